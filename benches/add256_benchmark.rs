@@ -0,0 +1,85 @@
+// The backend list shared with the tests
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[cfg(target_arch = "x86_64")]
+use common::supported_backends;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+#[cfg(target_arch = "x86_64")]
+use fast_eint::simd::{wrapping_add_256_with, wrapping_add_512_with};
+use fast_eint::{wrapping_add_256, wrapping_add_512};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+const BATCH_SIZES: [usize; 4] = [8, 32, 128, 1024];
+
+pub fn batch_add256_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch add256");
+    for len in BATCH_SIZES {
+        let mut rng = ChaCha20Rng::seed_from_u64(10000);
+        let mut buf_a = vec![0u8; 32 * len];
+        let mut buf_b = vec![0u8; 32 * len];
+        let mut buf_c = vec![0u8; 32 * len];
+        rng.fill_bytes(&mut buf_a);
+        rng.fill_bytes(&mut buf_b);
+
+        group.bench_with_input(BenchmarkId::new("fast", len), &len, |b, &len| {
+            b.iter(|| wrapping_add_256(buf_a.as_ptr(), buf_b.as_ptr(), buf_c.as_mut_ptr(), len))
+        });
+        // The simd backends only exist on x86_64
+        #[cfg(target_arch = "x86_64")]
+        for backend in supported_backends() {
+            let name = format!("{:?}", backend).to_lowercase();
+            group.bench_with_input(BenchmarkId::new(name, len), &len, |b, &len| {
+                b.iter(|| {
+                    wrapping_add_256_with(
+                        backend,
+                        buf_a.as_ptr(),
+                        buf_b.as_ptr(),
+                        buf_c.as_mut_ptr(),
+                        len,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+pub fn batch_add512_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch add512");
+    for len in BATCH_SIZES {
+        let mut rng = ChaCha20Rng::seed_from_u64(10000);
+        let mut buf_a = vec![0u8; 64 * len];
+        let mut buf_b = vec![0u8; 64 * len];
+        let mut buf_c = vec![0u8; 64 * len];
+        rng.fill_bytes(&mut buf_a);
+        rng.fill_bytes(&mut buf_b);
+
+        group.bench_with_input(BenchmarkId::new("fast", len), &len, |b, &len| {
+            b.iter(|| wrapping_add_512(buf_a.as_ptr(), buf_b.as_ptr(), buf_c.as_mut_ptr(), len))
+        });
+        // The simd backends only exist on x86_64
+        #[cfg(target_arch = "x86_64")]
+        for backend in supported_backends() {
+            let name = format!("{:?}", backend).to_lowercase();
+            group.bench_with_input(BenchmarkId::new(name, len), &len, |b, &len| {
+                b.iter(|| {
+                    wrapping_add_512_with(
+                        backend,
+                        buf_a.as_ptr(),
+                        buf_b.as_ptr(),
+                        buf_c.as_mut_ptr(),
+                        len,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch_add256_benchmark, batch_add512_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,140 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fast_eint::lane::{as_bytes, as_bytes_mut};
+use fast_eint::{
+    narrowing_right_shift_512, narrowing_right_shift_512_c, widening_mul_256_c,
+    widening_mul_256_strided, widening_mul_512, widening_mul_512_c, widening_sqr_256,
+    widening_sqr_256_c, wrapping_add_256, wrapping_add_256_c, wrapping_add_512, wrapping_add_512_c,
+    wrapping_mul_256, wrapping_mul_256_c, wrapping_mul_512, wrapping_mul_512_c, wrapping_sqr_256,
+    wrapping_sqr_256_c, wrapping_sub_256, wrapping_sub_256_c, wrapping_sub_512, wrapping_sub_512_c,
+};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+const BATCH_RUNS: usize = 128;
+
+type Binary = fn(*const u8, *const u8, *mut u8, usize);
+
+fn widening_mul_256_asm(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, b, 32, dst, 64, len);
+}
+
+fn wrapping_sqr_256_asm(a: *const u8, _: *const u8, dst: *mut u8, len: usize) {
+    wrapping_sqr_256(a, dst, len);
+}
+
+fn wrapping_sqr_256_c_bench(a: *const u8, _: *const u8, dst: *mut u8, len: usize) {
+    wrapping_sqr_256_c(a, dst, len);
+}
+
+fn widening_sqr_256_asm(a: *const u8, _: *const u8, dst: *mut u8, len: usize) {
+    widening_sqr_256(a, dst, len);
+}
+
+fn widening_sqr_256_c_bench(a: *const u8, _: *const u8, dst: *mut u8, len: usize) {
+    widening_sqr_256_c(a, dst, len);
+}
+
+/// Operation name, operand and result bytes, then the asm and C kernels.
+const KERNELS: &[(&str, usize, usize, Binary, Binary)] = &[
+    ("add256", 32, 32, wrapping_add_256, wrapping_add_256_c),
+    ("add512", 64, 64, wrapping_add_512, wrapping_add_512_c),
+    ("sub256", 32, 32, wrapping_sub_256, wrapping_sub_256_c),
+    ("sub512", 64, 64, wrapping_sub_512, wrapping_sub_512_c),
+    (
+        "wrapping mul256",
+        32,
+        32,
+        wrapping_mul_256,
+        wrapping_mul_256_c,
+    ),
+    (
+        "wrapping mul512",
+        64,
+        64,
+        wrapping_mul_512,
+        wrapping_mul_512_c,
+    ),
+    (
+        "widening mul256",
+        32,
+        64,
+        widening_mul_256_asm,
+        widening_mul_256_c,
+    ),
+    (
+        "widening mul512",
+        64,
+        128,
+        widening_mul_512,
+        widening_mul_512_c,
+    ),
+    (
+        "wrapping sqr256",
+        32,
+        32,
+        wrapping_sqr_256_asm,
+        wrapping_sqr_256_c_bench,
+    ),
+    (
+        "widening sqr256",
+        32,
+        64,
+        widening_sqr_256_asm,
+        widening_sqr_256_c_bench,
+    ),
+];
+
+pub fn asm_vs_c_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("asm vs c");
+    for &(name, width, out, asm, reference) in KERNELS {
+        let mut rng = ChaCha20Rng::seed_from_u64(10000);
+        let mut buf_a = vec![0u8; width * BATCH_RUNS];
+        let mut buf_b = vec![0u8; width * BATCH_RUNS];
+        let mut buf_c = vec![0u8; out * BATCH_RUNS];
+        rng.fill_bytes(&mut buf_a);
+        rng.fill_bytes(&mut buf_b);
+
+        for (backend, f) in [("asm", asm), ("c", reference)] {
+            group.bench_function(BenchmarkId::new(name, backend), |b| {
+                b.iter(|| {
+                    f(
+                        buf_a.as_ptr(),
+                        buf_b.as_ptr(),
+                        buf_c.as_mut_ptr(),
+                        BATCH_RUNS,
+                    )
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+pub fn c_batch_narrowing_right_shift_512_benchmark(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::seed_from_u64(10000);
+    let mut buf = vec![0u64; 8 * BATCH_RUNS];
+    rng.fill_bytes(as_bytes_mut(&mut buf));
+    let mut result = vec![0u64; 4 * BATCH_RUNS];
+    let (src, dst) = (
+        as_bytes(&buf).as_ptr(),
+        as_bytes_mut(&mut result).as_mut_ptr(),
+    );
+
+    let mut group = c.benchmark_group("asm vs c");
+    group.bench_function(BenchmarkId::new("narrowing_right_shift_512", "rust"), |b| {
+        b.iter(|| narrowing_right_shift_512(src, dst, 111, BATCH_RUNS))
+    });
+    group.bench_function(BenchmarkId::new("narrowing_right_shift_512", "c"), |b| {
+        b.iter(|| narrowing_right_shift_512_c(src, dst, 111, BATCH_RUNS))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    asm_vs_c_benchmark,
+    c_batch_narrowing_right_shift_512_benchmark
+);
+criterion_main!(benches);
@@ -1,7 +1,16 @@
+// The backend list shared with the tests
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[cfg(target_arch = "x86_64")]
+use common::supported_backends;
 use criterion::{criterion_group, criterion_main, Criterion};
 use eint::{Eint, E256, E512};
+#[cfg(target_arch = "x86_64")]
+use fast_eint::simd::{widening_mul_256_with, wrapping_mul_256_with};
 use fast_eint::{
-    narrowing_right_shift_512, narrowing_right_shift_512_c, widening_mul_256, wrapping_mul_256,
+    narrowing_right_shift_512, widening_mul_256, widening_sqr_256, wrapping_mul_256,
+    wrapping_sqr_256,
 };
 use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
@@ -28,6 +37,21 @@ pub fn fast_batch_mul256_benchmark(c: &mut Criterion) {
     });
 }
 
+pub fn fast_batch_sqr256_benchmark(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::seed_from_u64(10000);
+    let mut buf_a = vec![0u8; 32 * BATCH_RUNS];
+    let mut buf_wide = vec![0u8; 64 * BATCH_RUNS];
+    let mut buf_narrow = vec![0u8; 32 * BATCH_RUNS];
+    rng.fill_bytes(&mut buf_a);
+
+    c.bench_function("fast batch sqr256", |b| {
+        b.iter(|| widening_sqr_256(buf_a.as_ptr(), buf_wide.as_mut_ptr(), BATCH_RUNS))
+    });
+    c.bench_function("fast batch wrapping sqr256", |b| {
+        b.iter(|| wrapping_sqr_256(buf_a.as_ptr(), buf_narrow.as_mut_ptr(), BATCH_RUNS))
+    });
+}
+
 pub fn normal_batch_mul256_benchmark(c: &mut Criterion) {
     c.bench_function("normal batch mul256", |b| {
         let mut rng = ChaCha20Rng::seed_from_u64(10000);
@@ -96,19 +120,6 @@ pub fn fast_batch_narrowing_right_shift_512_benchmark(c: &mut Criterion) {
     });
 }
 
-pub fn c_batch_narrowing_right_shift_512_benchmark(c: &mut Criterion) {
-    c.bench_function("c batch narrowing_right_shift_512", |b| {
-        let mut rng = ChaCha20Rng::seed_from_u64(10000);
-        let mut buf = vec![0u8; 64 * BATCH_RUNS];
-        rng.fill_bytes(&mut buf);
-        let mut result = vec![0u8; 32 * BATCH_RUNS];
-
-        b.iter(|| {
-            narrowing_right_shift_512_c(buf.as_ptr(), result.as_mut_ptr(), 111, BATCH_RUNS);
-        })
-    });
-}
-
 pub fn normal_batch_narrowing_right_shift_512_benchmark(c: &mut Criterion) {
     c.bench_function("normal batch narrowing_right_shift_512", |b| {
         let mut rng = ChaCha20Rng::seed_from_u64(10000);
@@ -170,16 +181,76 @@ pub fn normal_batch_wrapping_mul256_benchmark(c: &mut Criterion) {
     });
 }
 
+#[cfg(target_arch = "x86_64")]
+pub fn simd_batch_mul256_benchmark(c: &mut Criterion) {
+    for backend in supported_backends() {
+        let name = format!("{:?}", backend).to_lowercase();
+        c.bench_function(&format!("{} batch mul256", name), |b| {
+            let mut rng = ChaCha20Rng::seed_from_u64(10000);
+            let mut buf_a = vec![0u8; 32 * BATCH_RUNS];
+            let mut buf_b = vec![0u8; 32 * BATCH_RUNS];
+            let mut buf_c = vec![0u8; 64 * BATCH_RUNS];
+            rng.fill_bytes(&mut buf_a);
+            rng.fill_bytes(&mut buf_b);
+
+            b.iter(|| {
+                widening_mul_256_with(
+                    backend,
+                    buf_a.as_ptr(),
+                    buf_b.as_ptr(),
+                    buf_c.as_mut_ptr(),
+                    BATCH_RUNS,
+                );
+            })
+        });
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+pub fn simd_batch_wrapping_mul256_benchmark(c: &mut Criterion) {
+    for backend in supported_backends() {
+        let name = format!("{:?}", backend).to_lowercase();
+        c.bench_function(&format!("{} batch wrapping mul256", name), |b| {
+            let mut rng = ChaCha20Rng::seed_from_u64(10000);
+            let mut buf_a = vec![0u8; 32 * BATCH_RUNS];
+            let mut buf_b = vec![0u8; 32 * BATCH_RUNS];
+            let mut buf_c = vec![0u8; 32 * BATCH_RUNS];
+            rng.fill_bytes(&mut buf_a);
+            rng.fill_bytes(&mut buf_b);
+
+            b.iter(|| {
+                wrapping_mul_256_with(
+                    backend,
+                    buf_a.as_ptr(),
+                    buf_b.as_ptr(),
+                    buf_c.as_mut_ptr(),
+                    BATCH_RUNS,
+                );
+            })
+        });
+    }
+}
+
 criterion_group!(
     benches,
     normal_single_mul256_benchmark,
     fast_single_mul256_benchmark,
     normal_batch_mul256_benchmark,
     fast_batch_mul256_benchmark,
+    fast_batch_sqr256_benchmark,
     normal_batch_narrowing_right_shift_512_benchmark,
     fast_batch_narrowing_right_shift_512_benchmark,
-    c_batch_narrowing_right_shift_512_benchmark,
     normal_batch_wrapping_mul256_benchmark,
     fast_batch_wrapping_mul256_benchmark,
 );
+// The simd backends only exist on x86_64
+#[cfg(target_arch = "x86_64")]
+criterion_group!(
+    simd_benches,
+    simd_batch_mul256_benchmark,
+    simd_batch_wrapping_mul256_benchmark,
+);
+#[cfg(target_arch = "x86_64")]
+criterion_main!(benches, simd_benches);
+#[cfg(not(target_arch = "x86_64"))]
 criterion_main!(benches);
@@ -0,0 +1,92 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fast_eint::lane::{as_bytes_mut, U1024, U2048, U512};
+use fast_eint::wide::{
+    widening_mul_1024, widening_mul_1024_comba, widening_mul_512, widening_mul_512_comba,
+    wrapping_mul_1024, wrapping_mul_1024_comba, wrapping_mul_512, wrapping_mul_512_comba,
+};
+use fast_eint::{
+    widening_mul_512 as asm_widening_mul_512, wrapping_mul_512 as asm_wrapping_mul_512,
+};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+const BATCH_SIZES: [usize; 3] = [8, 128, 1024];
+
+pub fn batch_mul512_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch mul512");
+    for len in BATCH_SIZES {
+        let mut rng = ChaCha20Rng::seed_from_u64(10000);
+        let mut buf_a = vec![U512::ZERO; len];
+        let mut buf_b = vec![U512::ZERO; len];
+        let mut buf_c = vec![U1024::ZERO; len];
+        let mut buf_d = vec![U512::ZERO; len];
+        rng.fill_bytes(as_bytes_mut(&mut buf_a));
+        rng.fill_bytes(as_bytes_mut(&mut buf_b));
+
+        group.bench_with_input(BenchmarkId::new("widening karatsuba", len), &len, |b, _| {
+            b.iter(|| widening_mul_512(&buf_a, &buf_b, &mut buf_c))
+        });
+        group.bench_with_input(BenchmarkId::new("widening comba", len), &len, |b, _| {
+            b.iter(|| widening_mul_512_comba(&buf_a, &buf_b, &mut buf_c))
+        });
+        group.bench_with_input(BenchmarkId::new("widening asm", len), &len, |b, &len| {
+            b.iter(|| {
+                asm_widening_mul_512(
+                    buf_a.as_ptr() as *const u8,
+                    buf_b.as_ptr() as *const u8,
+                    buf_c.as_mut_ptr() as *mut u8,
+                    len,
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("wrapping karatsuba", len), &len, |b, _| {
+            b.iter(|| wrapping_mul_512(&buf_a, &buf_b, &mut buf_d))
+        });
+        group.bench_with_input(BenchmarkId::new("wrapping comba", len), &len, |b, _| {
+            b.iter(|| wrapping_mul_512_comba(&buf_a, &buf_b, &mut buf_d))
+        });
+        group.bench_with_input(BenchmarkId::new("wrapping asm", len), &len, |b, &len| {
+            b.iter(|| {
+                asm_wrapping_mul_512(
+                    buf_a.as_ptr() as *const u8,
+                    buf_b.as_ptr() as *const u8,
+                    buf_d.as_mut_ptr() as *mut u8,
+                    len,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+pub fn batch_mul1024_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch mul1024");
+    for len in BATCH_SIZES {
+        let mut rng = ChaCha20Rng::seed_from_u64(10000);
+        let mut buf_a = vec![U1024::ZERO; len];
+        let mut buf_b = vec![U1024::ZERO; len];
+        let mut buf_c = vec![U2048::ZERO; len];
+        let mut buf_d = vec![U1024::ZERO; len];
+        rng.fill_bytes(as_bytes_mut(&mut buf_a));
+        rng.fill_bytes(as_bytes_mut(&mut buf_b));
+
+        group.bench_with_input(BenchmarkId::new("widening karatsuba", len), &len, |b, _| {
+            b.iter(|| widening_mul_1024(&buf_a, &buf_b, &mut buf_c))
+        });
+        group.bench_with_input(BenchmarkId::new("widening comba", len), &len, |b, _| {
+            b.iter(|| widening_mul_1024_comba(&buf_a, &buf_b, &mut buf_c))
+        });
+        group.bench_with_input(BenchmarkId::new("wrapping karatsuba", len), &len, |b, _| {
+            b.iter(|| wrapping_mul_1024(&buf_a, &buf_b, &mut buf_d))
+        });
+        group.bench_with_input(BenchmarkId::new("wrapping comba", len), &len, |b, _| {
+            b.iter(|| wrapping_mul_1024_comba(&buf_a, &buf_b, &mut buf_d))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch_mul512_benchmark, batch_mul1024_benchmark);
+criterion_main!(benches);
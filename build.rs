@@ -1,9 +1,928 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
 fn main() {
-    cc::Build::new()
-        .file("src/lib.c")
-        .static_flag(true)
-        .flag("-O3")
-        .flag("-Wall")
-        .flag("-Werror")
-        .compile("fasteint-c");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Opt-in, so that the default build needs no C toolchain. Warnings stay
+    // warnings, a newer compiler must not break downstream builds. There is
+    // no `.static_flag(true)`: cc always builds a static archive, and the
+    // method is deprecated as a no-op.
+    #[cfg(feature = "c-reference")]
+    {
+        println!("cargo:rerun-if-changed=src/lib.c");
+        println!("cargo:rerun-if-changed=src/reference.c");
+        cc::Build::new()
+            .file("src/lib.c")
+            .file("src/reference.c")
+            .flag("-O3")
+            .flag("-Wall")
+            .compile("fasteint-c");
+    }
+
+    let out = env::var("OUT_DIR").unwrap();
+    let out = Path::new(&out);
+    fs::write(out.join("kernels_x86_64.rs"), generate(Arch::X86_64)).unwrap();
+    fs::write(out.join("kernels_aarch64.rs"), generate(Arch::Aarch64)).unwrap();
+    fs::write(out.join("model_x86_64.rs"), model()).unwrap();
+
+    let exports = exports();
+    fs::write(out.join("capi.rs"), capi(&exports)).unwrap();
+    fs::write(out.join("fast_eint.h"), header(&exports)).unwrap();
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    WrappingMul,
+    WideningMul,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Arch {
+    X86_64,
+    Aarch64,
+}
+
+/// Kernels emitted into `kernels_<arch>.rs`, as operation and limb count.
+/// Adding a width is one line here.
+const KERNELS: &[(Op, usize)] = &[
+    (Op::Add, 4),
+    (Op::Add, 8),
+    (Op::Sub, 4),
+    (Op::Sub, 8),
+    (Op::WrappingMul, 4),
+    (Op::WrappingMul, 8),
+    (Op::WideningMul, 4),
+    (Op::WideningMul, 8),
+];
+
+/// Multiplication keeps a whole row of partial products in r8.. while this
+/// many limbs still leave a register for the row's carry out.
+const MAX_ROW_LIMBS: usize = 7;
+
+fn generate(arch: Arch) -> String {
+    let mut s = String::from("// Generated by build.rs, do not edit.\n");
+    for &(op, n) in KERNELS {
+        let name = name(op, n);
+        let dst_bytes = if op == Op::WideningMul { 16 * n } else { 8 * n };
+        // widening_mul_256 predates this and keeps its own byte-offset entry
+        // point in lib.rs.
+        if !(op == Op::WideningMul && n == 4) {
+            contiguous(&mut s, &name, 8 * n, dst_bytes);
+        }
+        strided(&mut s, &name);
+
+        if arch == Arch::Aarch64 {
+            let lines = match op {
+                Op::Add => a64_carry_chain(n, "adds", "adcs"),
+                Op::Sub => a64_carry_chain(n, "subs", "sbcs"),
+                Op::WrappingMul | Op::WideningMul => a64_mul_columns(n, op == Op::WideningMul),
+            };
+            a64_one(&mut s, &name, &lines);
+            continue;
+        }
+        let (lines, regs) = x86_64_lines(op, n);
+        one(&mut s, &name, &lines, regs);
+    }
+    s
+}
+
+/// The x86_64 asm of one element and the number of r8.. registers it uses.
+fn x86_64_lines(op: Op, n: usize) -> (Vec<String>, usize) {
+    match op {
+        Op::Add => carry_chain(n, "add", "adc"),
+        Op::Sub => carry_chain(n, "sub", "sbb"),
+        Op::WrappingMul | Op::WideningMul if n <= MAX_ROW_LIMBS => {
+            mul_rows(n, op == Op::WideningMul)
+        }
+        Op::WrappingMul | Op::WideningMul => mul_memory(n, op == Op::WideningMul),
+    }
+}
+
+/// The same x86_64 asm as Rust calls on the small machine model in
+/// src/proofs.rs, one call per instruction, so Kani can check the exact
+/// instruction sequence that `generate` emits. Only the forms the
+/// generators above use are understood, anything else fails the build.
+fn model() -> String {
+    let mut s = String::from("// Generated by build.rs, do not edit.\n");
+    for &(op, n) in KERNELS {
+        writeln!(s, "\npub(super) fn {}(m: &mut Machine) {{", name(op, n)).unwrap();
+        for line in x86_64_lines(op, n).0.iter().filter(|l| !l.is_empty()) {
+            let (mnemonic, operands) = line.split_once(' ').unwrap();
+            let operands: Vec<String> = operands.split(", ").map(model_operand).collect();
+            writeln!(s, "    m.{}({});", mnemonic, operands.join(", ")).unwrap();
+        }
+        s.push_str("}\n");
+    }
+    s
+}
+
+fn model_operand(operand: &str) -> String {
+    let operand = operand.trim_start_matches("qword ptr ");
+    if let Some(address) = operand.strip_prefix('[') {
+        let (base, offset) = address.trim_end_matches(']').split_once(" + ").unwrap();
+        let base = match base {
+            "rsi" => "A",
+            "rcx" => "B",
+            "rdi" => "Dst",
+            _ => panic!("no model for base register {}", base),
+        };
+        let offset: usize = offset.parse().unwrap();
+        assert!(offset.is_multiple_of(8));
+        return format!("Mem({}, {})", base, offset / 8);
+    }
+    match operand {
+        "rax" => "RAX".to_string(),
+        "rdx" => "RDX".to_string(),
+        "0" => "Imm(0)".to_string(),
+        r => match r.strip_prefix('r').and_then(|n| n.parse::<usize>().ok()) {
+            Some(n) if (8..16).contains(&n) => format!("Reg({})", n),
+            _ => panic!("no model for operand {}", r),
+        },
+    }
+}
+
+fn name(op: Op, n: usize) -> String {
+    let prefix = match op {
+        Op::Add => "wrapping_add",
+        Op::Sub => "wrapping_sub",
+        Op::WrappingMul => "wrapping_mul",
+        Op::WideningMul => "widening_mul",
+    };
+    format!("{}_{}", prefix, 64 * n)
+}
+
+fn contiguous(s: &mut String, name: &str, src_bytes: usize, dst_bytes: usize) {
+    write!(
+        s,
+        r#"
+#[inline(never)]
+pub fn {name}(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {{
+    for i in 0..len {{
+        unsafe {{
+            {name}_one(
+                a.wrapping_add(i * {src_bytes}),
+                b.wrapping_add(i * {src_bytes}),
+                dst.wrapping_add(i * {dst_bytes}),
+            );
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+}
+
+fn strided(s: &mut String, name: &str) {
+    write!(
+        s,
+        r#"
+#[inline(never)]
+pub fn {name}_strided(
+    a: *const u8,
+    a_stride: isize,
+    b: *const u8,
+    b_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {{
+    for i in 0..len as isize {{
+        unsafe {{
+            {name}_one(
+                a.wrapping_offset(i * a_stride),
+                b.wrapping_offset(i * b_stride),
+                dst.wrapping_offset(i * dst_stride),
+            );
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+}
+
+fn one(s: &mut String, name: &str, lines: &[String], regs: usize) {
+    write!(
+        s,
+        "\n#[inline(always)]\nunsafe fn {name}_one(a: *const u8, b: *const u8, dst: *mut u8) {{\n    asm!(\n"
+    )
+    .unwrap();
+    for line in lines {
+        writeln!(s, "        \"{}\",", line).unwrap();
+    }
+    s.push_str(
+        "        in(\"rsi\") a as usize,\n        in(\"rcx\") b as usize,\n        in(\"rdi\") dst as usize,\n",
+    );
+    // r12 and up are callee-saved, so the ABI clobbers do not cover them
+    for r in 12..8 + regs {
+        writeln!(s, "        lateout(\"r{}\") _,", r).unwrap();
+    }
+    s.push_str("        clobber_abi(\"sysv64\"),\n        clobber_abi(\"win64\"),\n    );\n}\n");
+}
+
+/// Add or subtract 4 limbs at a time through r8..r11. `mov` leaves the
+/// flags alone, so the carry survives across groups.
+fn carry_chain(n: usize, first: &str, rest: &str) -> (Vec<String>, usize) {
+    let mut lines = vec![];
+    for group in (0..n).step_by(4) {
+        let limbs = group..(group + 4).min(n);
+        for (r, l) in limbs.clone().enumerate() {
+            lines.push(format!("mov r{}, [rsi + {}]", 8 + r, 8 * l));
+        }
+        for (r, l) in limbs.clone().enumerate() {
+            let op = if l == 0 { first } else { rest };
+            lines.push(format!("{} r{}, [rcx + {}]", op, 8 + r, 8 * l));
+        }
+        for (r, l) in limbs.enumerate() {
+            lines.push(format!("mov [rdi + {}], r{}", 8 * l, 8 + r));
+        }
+    }
+    (lines, 4.min(n))
+}
+
+/// Operand scanning with one row in registers, as in
+/// https://github.com/cloudflare/bn256/blob/9bd9f73a0273ed2f42707ed13b3e36d38baa2a49/mul_amd64.h#L1
+/// Row `i` is `a[i] * b` in r8..r(8+n), then added onto what the previous
+/// rows left in dst. The wrapping form drops every product past limb n - 1.
+fn mul_rows(n: usize, widening: bool) -> (Vec<String>, usize) {
+    let mut lines = vec![];
+    for i in 0..n {
+        let width = if widening { n } else { n - i };
+        for j in 0..width {
+            lines.push(format!("mov rax, [rsi + {}]", 8 * i));
+            lines.push(format!("mul qword ptr [rcx + {}]", 8 * j));
+            let last = !widening && j == width - 1;
+            match (j, last) {
+                (0, true) => lines.push("mov r8, rax".to_string()),
+                (0, false) => {
+                    lines.push("mov r8, rax".to_string());
+                    lines.push("mov r9, rdx".to_string());
+                }
+                (_, true) => lines.push(format!("add r{}, rax", 8 + j)),
+                (_, false) => {
+                    lines.push(format!("add r{}, rax", 8 + j));
+                    lines.push("adc rdx, 0".to_string());
+                    lines.push(format!("mov r{}, rdx", 9 + j));
+                }
+            }
+        }
+        // The widening row has one more register, its carry out
+        let regs = if widening { n + 1 } else { width };
+        lines.push(String::new());
+        if i > 0 {
+            for r in 0..width {
+                let op = if r == 0 { "add" } else { "adc" };
+                lines.push(format!("{} r{}, [rdi + {}]", op, 8 + r, 8 * (i + r)));
+            }
+            if widening {
+                lines.push(format!("adc r{}, 0", 8 + n));
+            }
+        }
+        for r in 0..regs {
+            lines.push(format!("mov [rdi + {}], r{}", 8 * (i + r), 8 + r));
+        }
+        if i + 1 < n {
+            lines.push(String::new());
+        }
+    }
+    (lines, n + widening as usize)
+}
+
+/// Operand scanning for widths whose rows no longer fit in registers: each
+/// partial product is added straight into dst, with r8 carrying the high
+/// half over to the next limb.
+fn mul_memory(n: usize, widening: bool) -> (Vec<String>, usize) {
+    let mut lines = vec![];
+    for i in 0..n {
+        let width = if widening { n } else { n - i };
+        for j in 0..width {
+            let k = 8 * (i + j);
+            lines.push(format!("mov rax, [rsi + {}]", 8 * i));
+            lines.push(format!("mul qword ptr [rcx + {}]", 8 * j));
+            if j > 0 {
+                lines.push("add rax, r8".to_string());
+                lines.push("adc rdx, 0".to_string());
+            }
+            if i == 0 {
+                lines.push(format!("mov [rdi + {}], rax", k));
+            } else {
+                lines.push(format!("add [rdi + {}], rax", k));
+                lines.push("adc rdx, 0".to_string());
+            }
+            lines.push("mov r8, rdx".to_string());
+        }
+        if widening {
+            lines.push(format!("mov [rdi + {}], r8", 8 * (i + n)));
+        }
+        if i + 1 < n {
+            lines.push(String::new());
+        }
+    }
+    (lines, 1)
+}
+
+fn a64_one(s: &mut String, name: &str, lines: &[String]) {
+    write!(
+        s,
+        "\n#[inline(always)]\nunsafe fn {name}_one(a: *const u8, b: *const u8, dst: *mut u8) {{\n    asm!(\n"
+    )
+    .unwrap();
+    for line in lines {
+        writeln!(s, "        \"{}\",", line).unwrap();
+    }
+    // Everything runs in x3..x17, which the C ABI clobber covers
+    s.push_str(
+        "        in(\"x0\") a as usize,\n        in(\"x1\") b as usize,\n        in(\"x2\") dst as usize,\n        clobber_abi(\"C\"),\n    );\n}\n",
+    );
+}
+
+/// Loads or stores consecutive limbs at `base + offset`, in pairs where
+/// possible.
+fn a64_pairs(
+    lines: &mut Vec<String>,
+    pair: &str,
+    single: &str,
+    regs: &[usize],
+    base: &str,
+    offset: usize,
+) {
+    for (p, regs) in regs.chunks(2).enumerate() {
+        let at = offset + 16 * p;
+        match regs {
+            [x, y] => lines.push(format!("{} x{}, x{}, [{}, #{}]", pair, x, y, base, at)),
+            [x] => lines.push(format!("{} x{}, [{}, #{}]", single, x, base, at)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// `adds`/`adcs` (or `subs`/`sbcs`) 4 limbs at a time, a in x3..x6 and b in
+/// x7..x10. Loads and stores leave the flags alone, so the carry survives
+/// across groups. On AArch64 the carry flag is the inverted borrow, which
+/// `sbcs` consumes as is.
+fn a64_carry_chain(n: usize, first: &str, rest: &str) -> Vec<String> {
+    let mut lines = vec![];
+    for group in (0..n).step_by(4) {
+        let limbs = (n - group).min(4);
+        let a: Vec<usize> = (3..3 + limbs).collect();
+        let b: Vec<usize> = (7..7 + limbs).collect();
+        a64_pairs(&mut lines, "ldp", "ldr", &a, "x0", 8 * group);
+        a64_pairs(&mut lines, "ldp", "ldr", &b, "x1", 8 * group);
+        for r in 0..limbs {
+            let op = if group + r == 0 { first } else { rest };
+            lines.push(format!("{} x{}, x{}, x{}", op, a[r], a[r], b[r]));
+        }
+        a64_pairs(&mut lines, "stp", "str", &a, "x2", 8 * group);
+    }
+    lines
+}
+
+/// Product scanning: column `k` sums `a[i] * b[k - i]` into a three limb
+/// accumulator with `mul`/`umulh` and `adds`/`adcs`/`adc`, stores its low
+/// limb and shifts the rest down by renaming registers. Both operands stay
+/// in registers while they fit next to the accumulator, so dst may then
+/// alias them.
+fn a64_mul_columns(n: usize, widening: bool) -> Vec<String> {
+    let mut lines = vec![];
+    let preload = 2 * n + 5 <= 15;
+    let (mut acc, lo, hi) = if preload {
+        // a in x3.., b right after it
+        let a: Vec<usize> = (3..3 + n).collect();
+        let b: Vec<usize> = (3 + n..3 + 2 * n).collect();
+        a64_pairs(&mut lines, "ldp", "ldr", &a, "x0", 0);
+        a64_pairs(&mut lines, "ldp", "ldr", &b, "x1", 0);
+        let r = 3 + 2 * n;
+        let acc = [r, r + 1, r + 2].map(|r| format!("x{}", r));
+        (acc, format!("x{}", r + 3), format!("x{}", r + 4))
+    } else {
+        let acc = ["x5", "x6", "x7"].map(String::from);
+        (acc, "x8".to_string(), "x9".to_string())
+    };
+    for r in &acc {
+        lines.push(format!("mov {}, xzr", r));
+    }
+
+    let columns = if widening { 2 * n } else { n };
+    for k in 0..columns {
+        lines.push(String::new());
+        let last = !widening && k == n - 1;
+        for i in k.saturating_sub(n - 1)..=k.min(n - 1) {
+            let j = k - i;
+            let (x, y) = if preload {
+                (format!("x{}", 3 + i), format!("x{}", 3 + n + j))
+            } else {
+                lines.push(format!("ldr x3, [x0, #{}]", 8 * i));
+                lines.push(format!("ldr x4, [x1, #{}]", 8 * j));
+                ("x3".to_string(), "x4".to_string())
+            };
+            lines.push(format!("mul {}, {}, {}", lo, x, y));
+            if last {
+                // Only the low limb of the last wrapping column survives
+                lines.push(format!("add {}, {}, {}", acc[0], acc[0], lo));
+                continue;
+            }
+            lines.push(format!("umulh {}, {}, {}", hi, x, y));
+            lines.push(format!("adds {}, {}, {}", acc[0], acc[0], lo));
+            lines.push(format!("adcs {}, {}, {}", acc[1], acc[1], hi));
+            lines.push(format!("adc {}, {}, xzr", acc[2], acc[2]));
+        }
+        lines.push(format!("str {}, [x2, #{}]", acc[0], 8 * k));
+        if k + 1 < columns {
+            lines.push(format!("mov {}, xzr", acc[0]));
+            acc.rotate_left(1);
+        }
+    }
+    lines
+}
+
+#[derive(Copy, Clone)]
+enum Shape {
+    /// `(a, b, dst, len)`
+    Binary,
+    /// `(a, dst, len)`
+    Unary,
+    /// `(src, dst, shift, len)`
+    Shift,
+    /// `(a, b) -> bool`
+    Compare,
+}
+
+/// One exported C function and the Rust expression it evaluates.
+struct Export {
+    name: String,
+    params: Vec<(&'static str, &'static str)>,
+    ret: Option<&'static str>,
+    body: String,
+    /// Only built for x86_64, where `crate::simd` exists.
+    x86_64: bool,
+}
+
+fn call(target: &str, params: &[(&'static str, &'static str)]) -> String {
+    let args: Vec<&str> = params.iter().map(|(n, _)| *n).collect();
+    format!("crate::{}({})", target, args.join(", "))
+}
+
+/// Everything exported, in header order.
+fn exports() -> Vec<Export> {
+    let mut exports = kernel_exports();
+    exports.extend(simd_exports());
+    exports.extend(lane_exports());
+    exports.extend(context_exports());
+    exports
+}
+
+/// Every raw pointer kernel, contiguous and strided.
+fn kernel_exports() -> Vec<Export> {
+    let mut kernels: Vec<(String, Shape)> = KERNELS
+        .iter()
+        .map(|&(op, n)| (name(op, n), Shape::Binary))
+        .collect();
+    kernels.push(("widening_sqr_256".to_string(), Shape::Unary));
+    kernels.push(("wrapping_sqr_256".to_string(), Shape::Unary));
+    kernels.push(("narrowing_right_shift_512".to_string(), Shape::Shift));
+    kernels.push(("msbc_256".to_string(), Shape::Compare));
+
+    let mut exports = vec![];
+    for (name, shape) in kernels {
+        let (params, strided): (Vec<_>, Vec<_>) = match shape {
+            Shape::Binary => (
+                vec![
+                    ("a", "*const u8"),
+                    ("b", "*const u8"),
+                    ("dst", "*mut u8"),
+                    ("len", "usize"),
+                ],
+                vec![
+                    ("a", "*const u8"),
+                    ("a_stride", "isize"),
+                    ("b", "*const u8"),
+                    ("b_stride", "isize"),
+                    ("dst", "*mut u8"),
+                    ("dst_stride", "isize"),
+                    ("len", "usize"),
+                ],
+            ),
+            Shape::Unary => (
+                vec![("a", "*const u8"), ("dst", "*mut u8"), ("len", "usize")],
+                vec![
+                    ("a", "*const u8"),
+                    ("a_stride", "isize"),
+                    ("dst", "*mut u8"),
+                    ("dst_stride", "isize"),
+                    ("len", "usize"),
+                ],
+            ),
+            Shape::Shift => (
+                vec![
+                    ("src", "*const u8"),
+                    ("dst", "*mut u8"),
+                    ("shift", "u32"),
+                    ("len", "usize"),
+                ],
+                vec![
+                    ("src", "*const u8"),
+                    ("src_stride", "isize"),
+                    ("dst", "*mut u8"),
+                    ("dst_stride", "isize"),
+                    ("shift", "u32"),
+                    ("len", "usize"),
+                ],
+            ),
+            Shape::Compare => (vec![("a", "*const u8"), ("b", "*const u8")], vec![]),
+        };
+        let ret = match shape {
+            Shape::Compare => Some("bool"),
+            _ => None,
+        };
+        // The Rust widening_mul_256 takes byte offsets into one buffer, C
+        // gets the pointer form behind it.
+        let body = if name == "widening_mul_256" {
+            format!("unsafe {{ {} }}", call(&format!("{}_impl", name), &params))
+        } else {
+            call(&name, &params)
+        };
+        exports.push(Export {
+            name: format!("fast_eint_{}", name),
+            params,
+            ret,
+            body,
+            x86_64: false,
+        });
+        if !strided.is_empty() {
+            exports.push(Export {
+                name: format!("fast_eint_{}_strided", name),
+                body: call(&format!("{}_strided", name), &strided),
+                params: strided,
+                ret,
+                x86_64: false,
+            });
+        }
+    }
+    exports
+}
+
+/// The runtime-dispatched vector entry points, and their `_with` forms
+/// taking a `FAST_EINT_BACKEND_*` constant.
+fn simd_exports() -> Vec<Export> {
+    let binary = [
+        ("a", "*const u8"),
+        ("b", "*const u8"),
+        ("dst", "*mut u8"),
+        ("len", "usize"),
+    ];
+    let mut exports = vec![];
+    for name in [
+        "widening_mul_256",
+        "wrapping_mul_256",
+        "wrapping_add_256",
+        "wrapping_sub_256",
+        "wrapping_add_512",
+    ] {
+        exports.push(Export {
+            name: format!("fast_eint_simd_{}", name),
+            params: binary.to_vec(),
+            ret: None,
+            body: call(&format!("simd::{}", name), &binary),
+            x86_64: true,
+        });
+        let mut params = vec![("backend", "u32")];
+        params.extend(binary);
+        exports.push(Export {
+            name: format!("fast_eint_simd_{}_with", name),
+            body: format!(
+                "crate::simd::{}_with(backend_from_c(backend), a, b, dst, len)",
+                name
+            ),
+            params,
+            ret: None,
+            x86_64: true,
+        });
+    }
+    exports
+}
+
+/// An argument of a kernel over lane slices, as seen from C.
+#[derive(Copy, Clone)]
+enum Arg {
+    /// `len` lanes, read
+    In(&'static str),
+    /// `len` lanes, written
+    Out(&'static str),
+    /// `len` lanes, read and written
+    InOut(&'static str),
+    /// `len` masks, read
+    Masks(&'static str),
+    /// `len` masks, written
+    MasksOut(&'static str),
+    /// A single lane, passed by reference
+    One(&'static str),
+    Shift(&'static str),
+}
+
+impl Arg {
+    fn param(self) -> (&'static str, &'static str) {
+        match self {
+            Arg::In(n) | Arg::One(n) => (n, "*const u8"),
+            Arg::Out(n) | Arg::InOut(n) => (n, "*mut u8"),
+            Arg::Masks(n) => (n, "*const u64"),
+            Arg::MasksOut(n) => (n, "*mut u64"),
+            Arg::Shift(n) => (n, "u32"),
+        }
+    }
+
+    fn expr(self) -> String {
+        match self {
+            Arg::In(n) | Arg::Masks(n) => format!("lanes({}.cast(), len)", n),
+            Arg::Out(n) | Arg::InOut(n) | Arg::MasksOut(n) => {
+                format!("lanes_mut({}.cast(), len)", n)
+            }
+            Arg::One(n) => format!("&lane({})", n),
+            Arg::Shift(n) => n.to_string(),
+        }
+    }
+}
+
+/// Exports a slice kernel or context method over `len` lanes. `target` is a
+/// path under the crate root, or a method name when `context` is set.
+fn lane_export(
+    name: &str,
+    context: Option<&'static str>,
+    target: &str,
+    args: &[Arg],
+    ret: Option<&'static str>,
+) -> Export {
+    let mut params = vec![];
+    let receiver = match context {
+        Some(ty) => {
+            params.push(("ctx", ty));
+            "(*ctx).".to_string()
+        }
+        None => "crate::".to_string(),
+    };
+    params.extend(args.iter().map(|a| a.param()));
+    params.push(("len", "usize"));
+    let exprs: Vec<String> = args.iter().map(|a| a.expr()).collect();
+    Export {
+        name: format!("fast_eint_{}", name),
+        params,
+        ret,
+        body: format!("unsafe {{ {}{}({}) }}", receiver, target, exprs.join(", ")),
+        x86_64: false,
+    }
+}
+
+/// The `wide` and `ct` kernels, which take lane slices in Rust.
+fn lane_exports() -> Vec<Export> {
+    use Arg::*;
+    let mut exports = vec![];
+    for name in [
+        "widening_mul_512",
+        "wrapping_mul_512",
+        "widening_mul_1024",
+        "wrapping_mul_1024",
+        "widening_mul_512_comba",
+        "wrapping_mul_512_comba",
+        "widening_mul_1024_comba",
+        "wrapping_mul_1024_comba",
+    ] {
+        exports.push(lane_export(
+            &format!("wide_{}", name),
+            None,
+            &format!("wide::{}", name),
+            &[In("a"), In("b"), Out("dst")],
+            None,
+        ));
+    }
+    for name in ["widening_sqr_512", "wrapping_sqr_512"] {
+        exports.push(lane_export(
+            &format!("wide_{}", name),
+            None,
+            &format!("wide::{}", name),
+            &[In("a"), Out("dst")],
+            None,
+        ));
+    }
+
+    let ct: [(&str, &[Arg]); 7] = [
+        ("eq_256", &[In("a"), In("b"), MasksOut("dst")]),
+        ("lt_256", &[In("a"), In("b"), MasksOut("dst")]),
+        ("select_256", &[Masks("mask"), In("a"), In("b"), Out("dst")]),
+        ("swap_256", &[Masks("mask"), InOut("a"), InOut("b")]),
+        ("wrapping_shl_256", &[In("a"), Shift("shift"), Out("dst")]),
+        ("wrapping_shr_256", &[In("a"), Shift("shift"), Out("dst")]),
+        (
+            "narrowing_right_shift_512",
+            &[In("src"), Out("dst"), Shift("shift")],
+        ),
+    ];
+    for (name, args) in ct {
+        exports.push(lane_export(
+            &format!("ct_{}", name),
+            None,
+            &format!("ct::{}", name),
+            args,
+            None,
+        ));
+    }
+    exports.push(Export {
+        name: "fast_eint_ct_mask_nonzero".to_string(),
+        params: vec![("x", "u64")],
+        ret: Some("u64"),
+        body: "crate::ct::mask_nonzero(x)".to_string(),
+        x86_64: false,
+    });
+    exports
+}
+
+/// The `modular` contexts as opaque heap handles, with every batch method.
+fn context_exports() -> Vec<Export> {
+    use Arg::*;
+    type Methods = &'static [(&'static str, &'static [Arg], Option<&'static str>)];
+    let contexts: [(&str, &str, &str, Methods); 3] = [
+        (
+            "modulus256",
+            "*const Modulus256",
+            "*mut Modulus256",
+            &[
+                ("reduce", &[In("a"), Out("dst")], None),
+                ("addmod", &[In("a"), In("b"), Out("dst")], None),
+                ("submod", &[In("a"), In("b"), Out("dst")], None),
+                ("mulmod", &[In("a"), In("b"), Out("dst")], None),
+            ],
+        ),
+        (
+            "montgomery256",
+            "*const Montgomery256",
+            "*mut Montgomery256",
+            &[
+                ("to_mont", &[In("a"), Out("dst")], None),
+                ("from_mont", &[In("a"), Out("dst")], None),
+                ("mont_mul", &[In("a"), In("b"), Out("dst")], None),
+                ("mont_sqr", &[In("a"), Out("dst")], None),
+                ("powmod", &[In("a"), One("e"), Out("dst")], None),
+                ("powmod_each", &[In("a"), In("e"), Out("dst")], None),
+                ("invert_prime", &[In("a"), Out("dst")], None),
+                ("invert", &[In("a"), Out("dst")], Some("bool")),
+            ],
+        ),
+        (
+            "barrett256",
+            "*const Barrett256",
+            "*mut Barrett256",
+            &[("reduce_512", &[In("a"), Out("dst")], None)],
+        ),
+    ];
+
+    let mut exports = vec![];
+    for (prefix, ptr, ptr_mut, methods) in contexts {
+        let ty = &ptr_mut["*mut ".len()..];
+        exports.push(Export {
+            name: format!("fast_eint_{}_new", prefix),
+            params: vec![("m", "*const u8")],
+            ret: Some(ptr_mut),
+            body: format!("unsafe {{ context_new({}::new(lane(m))) }}", ty),
+            x86_64: false,
+        });
+        exports.push(Export {
+            name: format!("fast_eint_{}_free", prefix),
+            params: vec![("ctx", ptr_mut)],
+            ret: None,
+            body: "unsafe { context_free(ctx) }".to_string(),
+            x86_64: false,
+        });
+        for &(method, args, ret) in methods {
+            exports.push(lane_export(
+                &format!("{}_{}", prefix, method),
+                Some(ptr),
+                method,
+                args,
+                ret,
+            ));
+        }
+    }
+    exports
+}
+
+fn capi(exports: &[Export]) -> String {
+    let mut s = String::from("// Generated by build.rs, do not edit.\n");
+    for e in exports {
+        let params: Vec<String> = e
+            .params
+            .iter()
+            .map(|(n, t)| format!("{}: {}", n, t))
+            .collect();
+        let ret = e.ret.map(|r| format!(" -> {}", r)).unwrap_or_default();
+        let cfg = if e.x86_64 {
+            "#[cfg(target_arch = \"x86_64\")]\n"
+        } else {
+            ""
+        };
+        write!(
+            s,
+            "\n{}#[no_mangle]\npub extern \"C\" fn {}({}){} {{\n    {}\n}}\n",
+            cfg,
+            e.name,
+            params.join(", "),
+            ret,
+            e.body
+        )
+        .unwrap();
+    }
+    s
+}
+
+fn c_type(t: &str) -> &'static str {
+    match t {
+        "*const u8" => "const uint8_t *",
+        "*mut u8" => "uint8_t *",
+        "isize" => "ptrdiff_t ",
+        "usize" => "size_t ",
+        "u32" => "uint32_t ",
+        "u64" => "uint64_t ",
+        "*const u64" => "const uint64_t *",
+        "*mut u64" => "uint64_t *",
+        "bool" => "bool",
+        "*const Modulus256" => "const fast_eint_modulus256 *",
+        "*mut Modulus256" => "fast_eint_modulus256 *",
+        "*const Montgomery256" => "const fast_eint_montgomery256 *",
+        "*mut Montgomery256" => "fast_eint_montgomery256 *",
+        "*const Barrett256" => "const fast_eint_barrett256 *",
+        "*mut Barrett256" => "fast_eint_barrett256 *",
+        _ => panic!("no C type for {}", t),
+    }
+}
+
+fn header(exports: &[Export]) -> String {
+    let mut s = String::from(
+        "/* Generated by build.rs, do not edit. */\n\
+         \n\
+         /*\n \
+         * Every batch operation of the crate: the raw pointer kernels and their\n \
+         * strided forms, the simd dispatchers (x86_64 only), the wide and ct\n \
+         * kernels, and the modular contexts behind opaque handles.\n \
+         *\n \
+         * Numbers are little-endian u64 limbs. The raw pointer kernels accept\n \
+         * any alignment. The wide, ct and modular functions view their buffers\n \
+         * as arrays of lanes: every buffer must be 8-byte aligned and outputs\n \
+         * must not overlap inputs. Breaking that, or passing an unsupported\n \
+         * backend, aborts the process.\n \
+         */\n\
+         \n\
+         #ifndef FAST_EINT_H\n\
+         #define FAST_EINT_H\n\
+         \n\
+         #include <stdbool.h>\n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n\
+         typedef struct fast_eint_modulus256 fast_eint_modulus256;\n\
+         typedef struct fast_eint_montgomery256 fast_eint_montgomery256;\n\
+         typedef struct fast_eint_barrett256 fast_eint_barrett256;\n\
+         \n",
+    );
+    let mut x86_64 = false;
+    for e in exports {
+        if e.x86_64 != x86_64 {
+            s.push_str(if e.x86_64 {
+                "\n#if defined(__x86_64__) || defined(_M_X64)\n\
+                 #define FAST_EINT_BACKEND_SCALAR 0\n\
+                 #define FAST_EINT_BACKEND_AVX2 1\n\
+                 #define FAST_EINT_BACKEND_AVX512 2\n\
+                 #define FAST_EINT_BACKEND_IFMA 3\n\n"
+            } else {
+                "#endif\n\n"
+            });
+            x86_64 = e.x86_64;
+        }
+        let params: Vec<String> = e
+            .params
+            .iter()
+            .map(|(n, t)| format!("{}{}", c_type(t), n))
+            .collect();
+        let ret = e.ret.map_or("void", c_type);
+        let sep = if ret.ends_with('*') { "" } else { " " };
+        writeln!(
+            s,
+            "{}{}{}({});",
+            ret.trim_end(),
+            sep,
+            e.name,
+            params.join(", ")
+        )
+        .unwrap();
+    }
+    if x86_64 {
+        s.push_str("#endif\n");
+    }
+    s.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* FAST_EINT_H */\n");
+    s
 }
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::lanes::{run_dyn, DynBatch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: DynBatch| run_dyn(&batch));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_mem, MemBatch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: MemBatch| run_mem(&batch));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::lanes::{run_modular, ModularBatch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: ModularBatch| run_modular(&batch));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_msbc, Batch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: Batch| run_msbc(&batch));
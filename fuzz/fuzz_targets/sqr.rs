@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_sqr, Batch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: Batch| run_sqr(&batch));
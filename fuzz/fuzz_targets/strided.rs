@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_strided, StridedBatch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: StridedBatch| run_strided(&batch));
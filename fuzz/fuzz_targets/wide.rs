@@ -0,0 +1,6 @@
+#![no_main]
+
+use fast_eint_fuzz::lanes::{run_wide, LaneBatch};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: LaneBatch| run_wide(&batch));
@@ -0,0 +1,8 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_binary, Batch, WRAPPING_ADD};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: Batch| {
+    run_binary(&WRAPPING_ADD[batch.variant as usize], &batch);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use fast_eint_fuzz::{run_binary, Batch, WRAPPING_MUL};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|batch: Batch| {
+    run_binary(&WRAPPING_MUL[batch.variant as usize], &batch);
+});
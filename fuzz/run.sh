@@ -0,0 +1,31 @@
+#!/bin/sh
+# Builds and runs one fuzz target with AddressSanitizer, the same way
+# `cargo fuzz run` does, for machines without cargo-fuzz:
+#
+#     fuzz/run.sh wrapping_mul -max_total_time=60
+#
+# Arguments after the target go to libFuzzer. Needs a nightly toolchain.
+# The C reference kernels are built with -fsanitize=address as well, the
+# inline asm cannot be instrumented and relies on the guard bytes instead.
+set -e
+cd "$(dirname "$0")"
+target=$1
+shift
+triple=$(rustc -vV | sed -n 's/^host: //p')
+
+CFLAGS="-fsanitize=address" \
+RUSTFLAGS="-Cpasses=sancov-module \
+-Cllvm-args=-sanitizer-coverage-level=4 \
+-Cllvm-args=-sanitizer-coverage-inline-8bit-counters \
+-Cllvm-args=-sanitizer-coverage-pc-table \
+-Cllvm-args=-sanitizer-coverage-trace-compares \
+--cfg fuzzing -Zsanitizer=address -Cdebug-assertions -Coverflow-checks" \
+    cargo +nightly build --release --target "$triple" --bin "$target"
+
+binary="target/$triple/release/$target"
+# A crash file replays on its own
+if [ -f "$1" ]; then
+    exec "$binary" "$@"
+fi
+mkdir -p "corpus/$target" "artifacts/$target"
+exec "$binary" -artifact_prefix="artifacts/$target/" "$@" "corpus/$target"
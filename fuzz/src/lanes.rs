@@ -0,0 +1,541 @@
+//! Drivers for the kernels over lane slices: `wide`, `ct`, `modular` and
+//! the generic `_dyn` kernels.
+//!
+//! These take safe slices, so ASan has nothing to add at their boundary.
+//! What the fuzzer still finds is wrong results, and out-of-bounds accesses
+//! from the raw pointer kernels they call underneath. References are eint
+//! where it has the operation, and plain limb loops otherwise.
+
+use arbitrary::Arbitrary;
+use core::cmp::Ordering;
+use eint::{Eint, E1024, E256, E512};
+use fast_eint::lane::{as_bytes, as_bytes_mut, Lane, U1024, U2048, U256, U512};
+use fast_eint::modular::{Barrett256, Modulus256, Montgomery256};
+use fast_eint::{ct, generic, wide};
+
+/// Elements per call. Small, since the modular references are slow.
+const MAX_LEN: usize = 8;
+
+#[derive(Arbitrary, Debug)]
+pub struct LaneBatch {
+    /// Selects the kernel within a target
+    pub op: u8,
+    /// Runs with `b` equal to `a`
+    pub same_operands: bool,
+    pub shift: u32,
+    pub data: Vec<u8>,
+}
+
+/// `len` lanes of `T` from `data`, starting `skip` bytes in and wrapping
+/// around, or zeros when `data` is empty.
+fn lanes<T: Lane>(data: &[u8], skip: usize, len: usize) -> Vec<T> {
+    let mut lanes = vec![T::default(); len];
+    if !data.is_empty() {
+        let bytes = as_bytes_mut(&mut lanes);
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = data[(skip + i) % data.len()];
+        }
+    }
+    lanes
+}
+
+impl LaneBatch {
+    fn len(&self, bytes: usize) -> usize {
+        (self.data.len() / bytes).min(MAX_LEN)
+    }
+
+    /// Operands `a` and `b` of `T`, with `b` equal to `a` if so chosen.
+    fn operands<T: Lane>(&self, len: usize) -> (Vec<T>, Vec<T>) {
+        let a = lanes(&self.data, 0, len);
+        let b = if self.same_operands {
+            a.clone()
+        } else {
+            lanes(&self.data, T::BYTES * len, len)
+        };
+        (a, b)
+    }
+}
+
+fn widening<E: Eint>(a: &[u8], b: &[u8], dst: &mut [u8]) {
+    let (lo, hi) = E::get(a).widening_mul_u(E::get(b));
+    let (dst_lo, dst_hi) = dst.split_at_mut(dst.len() / 2);
+    lo.put(dst_lo);
+    hi.put(dst_hi);
+}
+
+fn wrapping<E: Eint>(a: &[u8], b: &[u8], dst: &mut [u8]) {
+    E::get(a).wrapping_mul(E::get(b)).put(dst);
+}
+
+/// Runs a multiply on `T` lanes into `D` lanes and compares every element.
+fn check_mul<T: Lane, D: Lane>(
+    batch: &LaneBatch,
+    kernel: fn(&[T], &[T], &mut [D]),
+    reference: fn(&[u8], &[u8], &mut [u8]),
+) {
+    let len = batch.len(2 * T::BYTES);
+    let (a, b) = batch.operands::<T>(len);
+    let mut dst = vec![D::default(); len];
+    kernel(&a, &b, &mut dst);
+
+    let mut expected = vec![0u8; D::BYTES];
+    for i in 0..len {
+        reference(
+            &as_bytes(&a)[i * T::BYTES..(i + 1) * T::BYTES],
+            &as_bytes(&b)[i * T::BYTES..(i + 1) * T::BYTES],
+            &mut expected,
+        );
+        assert_eq!(
+            &as_bytes(&dst)[i * D::BYTES..(i + 1) * D::BYTES],
+            &expected[..],
+            "element {}",
+            i
+        );
+    }
+}
+
+/// Squares `a` and compares with the multiply of `a` by itself.
+fn check_sqr<T: Lane, D: Lane>(
+    batch: &LaneBatch,
+    kernel: fn(&[T], &mut [D]),
+    reference: fn(&[u8], &[u8], &mut [u8]),
+) {
+    let len = batch.len(T::BYTES);
+    let a = lanes::<T>(&batch.data, 0, len);
+    let mut dst = vec![D::default(); len];
+    kernel(&a, &mut dst);
+
+    let mut expected = vec![0u8; D::BYTES];
+    for i in 0..len {
+        let x = &as_bytes(&a)[i * T::BYTES..(i + 1) * T::BYTES];
+        reference(x, x, &mut expected);
+        assert_eq!(
+            &as_bytes(&dst)[i * D::BYTES..(i + 1) * D::BYTES],
+            &expected[..]
+        );
+    }
+}
+
+/// Every kernel in `wide`, Karatsuba and Comba.
+pub fn run_wide(batch: &LaneBatch) {
+    match batch.op % 10 {
+        0 => check_mul::<U512, U1024>(batch, wide::widening_mul_512, widening::<E512>),
+        1 => check_mul::<U512, U512>(batch, wide::wrapping_mul_512, wrapping::<E512>),
+        2 => check_mul::<U1024, U2048>(batch, wide::widening_mul_1024, widening::<E1024>),
+        3 => check_mul::<U1024, U1024>(batch, wide::wrapping_mul_1024, wrapping::<E1024>),
+        4 => check_mul::<U512, U1024>(batch, wide::widening_mul_512_comba, widening::<E512>),
+        5 => check_mul::<U512, U512>(batch, wide::wrapping_mul_512_comba, wrapping::<E512>),
+        6 => check_mul::<U1024, U2048>(batch, wide::widening_mul_1024_comba, widening::<E1024>),
+        7 => check_mul::<U1024, U1024>(batch, wide::wrapping_mul_1024_comba, wrapping::<E1024>),
+        8 => check_sqr::<U512, U1024>(batch, wide::widening_sqr_512, widening::<E512>),
+        _ => check_sqr::<U512, U512>(batch, wide::wrapping_sqr_512, wrapping::<E512>),
+    }
+}
+
+fn e256(a: &U256) -> E256 {
+    E256::get(&a.to_le_bytes())
+}
+
+fn mask(x: bool) -> u64 {
+    if x {
+        u64::MAX
+    } else {
+        0
+    }
+}
+
+/// Every kernel in `ct`. Masks for select and swap come from the data, and
+/// any non-zero byte counts as set.
+pub fn run_ct(batch: &LaneBatch) {
+    let len = batch.len(64);
+    let (a, b) = batch.operands::<U256>(len);
+    let masks: Vec<u64> = (0..len)
+        .map(|i| ct::mask_nonzero(batch.data.get(i).copied().unwrap_or(0) as u64))
+        .collect();
+    let mut dst = vec![U256::ZERO; len];
+    let mut flags = vec![0u64; len];
+
+    match batch.op % 7 {
+        0 => {
+            ct::eq_256(&a, &b, &mut flags);
+            for i in 0..len {
+                assert_eq!(flags[i], mask(a[i] == b[i]));
+            }
+        }
+        1 => {
+            ct::lt_256(&a, &b, &mut flags);
+            for i in 0..len {
+                assert_eq!(
+                    flags[i],
+                    mask(e256(&a[i]).cmp_u(&e256(&b[i])) == Ordering::Less)
+                );
+            }
+        }
+        2 => {
+            ct::select_256(&masks, &a, &b, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], if masks[i] != 0 { a[i] } else { b[i] });
+            }
+        }
+        3 => {
+            let (mut x, mut y) = (a.clone(), b.clone());
+            ct::swap_256(&masks, &mut x, &mut y);
+            for i in 0..len {
+                let swapped = masks[i] != 0;
+                assert_eq!(x[i], if swapped { b[i] } else { a[i] });
+                assert_eq!(y[i], if swapped { a[i] } else { b[i] });
+            }
+        }
+        4 => {
+            ct::wrapping_shl_256(&a, batch.shift, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], shifted(&a[i], batch.shift & 255, true));
+            }
+        }
+        5 => {
+            ct::wrapping_shr_256(&a, batch.shift, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], shifted(&a[i], batch.shift & 255, false));
+            }
+        }
+        _ => {
+            let src = lanes::<U512>(&batch.data, 0, len);
+            ct::narrowing_right_shift_512(&src, &mut dst, batch.shift);
+            for i in 0..len {
+                let mut expected = [0u8; 32];
+                E512::get(&src[i].to_le_bytes())
+                    .wrapping_shr(batch.shift & 511)
+                    .put_lo(&mut expected);
+                assert_eq!(dst[i].to_le_bytes(), expected);
+            }
+        }
+    }
+}
+
+fn shifted(a: &U256, shift: u32, left: bool) -> U256 {
+    let mut bytes = [0u8; 32];
+    let a = e256(a);
+    if left {
+        a.wrapping_shl(shift).put(&mut bytes);
+    } else {
+        a.wrapping_shr(shift).put(&mut bytes);
+    }
+    U256::from_le_bytes(&bytes)
+}
+
+/// `x mod m`, by bit-by-bit long division, independent of every reduction
+/// under test.
+fn rem(x: &[u64], m: &U256) -> U256 {
+    let mut r = [0u64; 5];
+    for bit in (0..x.len() * 64).rev() {
+        let mut carry = (x[bit / 64] >> (bit % 64)) & 1;
+        for limb in r.iter_mut() {
+            let next = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        let ge = (0..5)
+            .rev()
+            .map(|i| (r[i], if i < 4 { m.0[i] } else { 0 }))
+            .find(|(a, b)| a != b)
+            .is_none_or(|(a, b)| a > b);
+        if ge {
+            let mut borrow = 0u64;
+            for (i, limb) in r.iter_mut().enumerate() {
+                let b = if i < 4 { m.0[i] } else { 0 };
+                let (d, o1) = limb.overflowing_sub(b);
+                let (d, o2) = d.overflowing_sub(borrow);
+                *limb = d;
+                borrow = (o1 || o2) as u64;
+            }
+        }
+    }
+    U256([r[0], r[1], r[2], r[3]])
+}
+
+fn product(a: &U256, b: &U256) -> [u64; 8] {
+    let mut bytes = [0u8; 64];
+    widening::<E256>(&a.to_le_bytes(), &b.to_le_bytes(), &mut bytes);
+    U512::from_le_bytes(&bytes).0
+}
+
+fn mulmod(a: &U256, b: &U256, m: &U256) -> U256 {
+    rem(&product(a, b), m)
+}
+
+fn addmod(a: &U256, b: &U256, m: &U256) -> U256 {
+    let mut sum = [0u64; 5];
+    let mut bytes = [0u8; 32];
+    let (s, carry) = e256(a).overflowing_add_u(e256(b));
+    s.put(&mut bytes);
+    sum[..4].copy_from_slice(&U256::from_le_bytes(&bytes).0);
+    sum[4] = carry as u64;
+    rem(&sum, m)
+}
+
+fn powmod(a: &U256, e: &U256, m: &U256) -> U256 {
+    let mut acc = rem(&[1], m);
+    let a = rem(&a.0, m);
+    for bit in (0..256).rev() {
+        acc = mulmod(&acc, &acc, m);
+        if (e.0[bit / 64] >> (bit % 64)) & 1 == 1 {
+            acc = mulmod(&acc, &a, m);
+        }
+    }
+    acc
+}
+
+fn gcd(a: &U256, b: &U256) -> U256 {
+    let (mut a, mut b) = (*a, *b);
+    while b != U256::ZERO {
+        (a, b) = (b, rem(&a.0, &b));
+    }
+    a
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct ModularBatch {
+    pub op: u8,
+    pub modulus: [u64; 4],
+    /// Shared exponent for `powmod`
+    pub exponent: [u64; 4],
+    pub data: Vec<u8>,
+}
+
+/// Every batch method of the three contexts. The modulus is fuzzed too, so
+/// short and unbalanced ones get their share. `invert_prime` is left to the
+/// unit tests, since a random modulus is almost never prime.
+pub fn run_modular(batch: &ModularBatch) {
+    let m = U256(batch.modulus);
+    let len = (batch.data.len() / 64).min(MAX_LEN);
+    let a = lanes::<U256>(&batch.data, 0, len);
+    let b = lanes::<U256>(&batch.data, 32 * len, len);
+    let mut dst = vec![U256::ZERO; len];
+
+    let Some(modulus) = Modulus256::new(m) else {
+        assert!(m == U256::ZERO);
+        assert!(Barrett256::new(m).is_none() && Montgomery256::new(m).is_none());
+        return;
+    };
+    let reduced = |x: &[U256]| -> Vec<U256> { x.iter().map(|x| rem(&x.0, &m)).collect() };
+
+    match batch.op % 11 {
+        0 => {
+            modulus.reduce(&a, &mut dst);
+            assert_eq!(dst, reduced(&a));
+        }
+        1 => {
+            let (a, b) = (reduced(&a), reduced(&b));
+            modulus.addmod(&a, &b, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], addmod(&a[i], &b[i], &m));
+            }
+        }
+        2 => {
+            let (a, b) = (reduced(&a), reduced(&b));
+            modulus.submod(&a, &b, &mut dst);
+            for i in 0..len {
+                // (a - b) + b == a
+                assert_eq!(addmod(&dst[i], &b[i], &m), a[i]);
+                assert_eq!(rem(&dst[i].0, &m), dst[i]);
+            }
+        }
+        3 => {
+            modulus.mulmod(&a, &b, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], mulmod(&a[i], &b[i], &m));
+            }
+        }
+        4 => {
+            let barrett = Barrett256::new(m).unwrap();
+            let x = lanes::<U512>(&batch.data, 0, len);
+            barrett.reduce_512(&x, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], rem(&x[i].0, &m));
+            }
+        }
+        op => run_montgomery(op, &m, &a, &b, &U256(batch.exponent)),
+    }
+}
+
+fn run_montgomery(op: u8, m: &U256, a: &[U256], b: &[U256], e: &U256) {
+    let Some(mont) = Montgomery256::new(*m) else {
+        assert!(m.0[0] & 1 == 0);
+        return;
+    };
+    let len = a.len();
+    let mut am = vec![U256::ZERO; len];
+    let mut bm = vec![U256::ZERO; len];
+    let mut cm = vec![U256::ZERO; len];
+    let mut dst = vec![U256::ZERO; len];
+    mont.to_mont(a, &mut am);
+    mont.to_mont(b, &mut bm);
+
+    match op {
+        5 => {
+            mont.from_mont(&am, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], rem(&a[i].0, m));
+            }
+        }
+        6 => {
+            mont.mont_mul(&am, &bm, &mut cm);
+            mont.from_mont(&cm, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], mulmod(&a[i], &b[i], m));
+            }
+        }
+        7 => {
+            mont.mont_sqr(&am, &mut cm);
+            mont.from_mont(&cm, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], mulmod(&a[i], &a[i], m));
+            }
+        }
+        8 => {
+            mont.powmod(a, e, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], powmod(&a[i], e, m));
+            }
+        }
+        9 => {
+            mont.powmod_each(a, b, &mut dst);
+            for i in 0..len {
+                assert_eq!(dst[i], powmod(&a[i], &b[i], m));
+            }
+        }
+        _ => {
+            let all = mont.invert(a, &mut dst);
+            let mut expected_all = true;
+            for i in 0..len {
+                let invertible = gcd(m, &rem(&a[i].0, m)) == U256([1, 0, 0, 0]);
+                expected_all &= invertible;
+                if invertible {
+                    assert_eq!(mulmod(&a[i], &dst[i], m), rem(&[1], m));
+                } else {
+                    assert_eq!(dst[i], U256::ZERO);
+                }
+            }
+            assert_eq!(all, expected_all);
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct DynBatch {
+    pub op: u8,
+    /// Limbs per element, 1 to 32
+    pub limbs: u8,
+    pub shift: u32,
+    pub data: Vec<u8>,
+}
+
+/// Low `out` limbs of `a * b`, row by row.
+fn mul_limbs(a: &[u64], b: &[u64], out: usize) -> Vec<u64> {
+    let mut r = vec![0u64; out];
+    for i in 0..a.len() {
+        let mut carry = 0u64;
+        for j in 0..b.len() {
+            if i + j >= out {
+                break;
+            }
+            let t = a[i] as u128 * b[j] as u128 + r[i + j] as u128 + carry as u128;
+            r[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        if i + b.len() < out {
+            r[i + b.len()] = carry;
+        }
+    }
+    r
+}
+
+/// Bit `i` of `x`, zero outside it.
+fn bit(x: &[u64], i: isize) -> u64 {
+    if i < 0 || i as usize >= 64 * x.len() {
+        return 0;
+    }
+    (x[i as usize / 64] >> (i as usize % 64)) & 1
+}
+
+/// `x` shifted by `shift` bits, left for positive, one bit at a time.
+fn shift_limbs(x: &[u64], shift: isize) -> Vec<u64> {
+    let mut r = vec![0u64; x.len()];
+    for i in 0..64 * x.len() {
+        r[i / 64] |= bit(x, i as isize - shift) << (i % 64);
+    }
+    r
+}
+
+/// The `_dyn` kernels of `generic` at every limb count up to 32. The const
+/// generic ones share their code and are covered with them.
+pub fn run_dyn(batch: &DynBatch) {
+    let limbs = batch.limbs as usize % 32 + 1;
+    let len = (batch.data.len() / (16 * limbs)).min(MAX_LEN);
+    let a = lanes::<u64>(&batch.data, 0, len * limbs);
+    let b = lanes::<u64>(&batch.data, 8 * len * limbs, len * limbs);
+    let mut dst = vec![0u64; len * limbs];
+    let elements = |x: &[u64]| -> Vec<Vec<u64>> { x.chunks(limbs).map(|c| c.to_vec()).collect() };
+    let (ea, eb) = (elements(&a), elements(&b));
+    let bits = (batch.shift as usize % (64 * limbs)) as isize;
+
+    let expected: Vec<u64> = match batch.op % 7 {
+        0 => {
+            generic::wrapping_add_dyn(&a, &b, &mut dst, limbs);
+            let mut sum = vec![];
+            for (x, y) in ea.iter().zip(&eb) {
+                let mut carry = 0u128;
+                for k in 0..limbs {
+                    carry += x[k] as u128 + y[k] as u128;
+                    sum.push(carry as u64);
+                    carry >>= 64;
+                }
+            }
+            sum
+        }
+        1 => {
+            generic::wrapping_sub_dyn(&a, &b, &mut dst, limbs);
+            // a - b + b == a
+            let mut back = vec![0u64; len * limbs];
+            generic::wrapping_add_dyn(&dst, &b, &mut back, limbs);
+            assert_eq!(back, a);
+            return;
+        }
+        2 => {
+            generic::wrapping_mul_dyn(&a, &b, &mut dst, limbs);
+            ea.iter()
+                .zip(&eb)
+                .flat_map(|(x, y)| mul_limbs(x, y, limbs))
+                .collect()
+        }
+        3 => {
+            let mut wide = vec![0u64; 2 * len * limbs];
+            generic::widening_mul_dyn(&a, &b, &mut wide, limbs);
+            let expected: Vec<u64> = ea
+                .iter()
+                .zip(&eb)
+                .flat_map(|(x, y)| mul_limbs(x, y, 2 * limbs))
+                .collect();
+            assert_eq!(wide, expected);
+            return;
+        }
+        4 => {
+            let mut orderings = vec![Ordering::Equal; len];
+            generic::cmp_dyn(&a, &b, &mut orderings, limbs);
+            for i in 0..len {
+                let expected = ea[i].iter().rev().cmp(eb[i].iter().rev());
+                assert_eq!(orderings[i], expected);
+            }
+            return;
+        }
+        5 => {
+            generic::wrapping_shl_dyn(&a, batch.shift, &mut dst, limbs);
+            ea.iter().flat_map(|x| shift_limbs(x, bits)).collect()
+        }
+        _ => {
+            generic::wrapping_shr_dyn(&a, batch.shift, &mut dst, limbs);
+            ea.iter().flat_map(|x| shift_limbs(x, -bits)).collect()
+        }
+    };
+    assert_eq!(dst, expected);
+}
@@ -0,0 +1,474 @@
+//! Shared driver for the fuzz targets in fuzz_targets/.
+//!
+//! The pointer kernel targets decode a [`Batch`] (or a [`StridedBatch`] or
+//! [`MemBatch`]): which backend to run, byte offsets for each buffer, how
+//! the buffers overlap, and the operand bytes. Each operand buffer is
+//! allocated at exactly the size the kernel may read, so AddressSanitizer
+//! catches accesses past its end from Rust and C. Inline asm is not
+//! instrumented, so `dst` also gets guard bytes on both sides which must
+//! come back untouched. Results are compared with eint. The slice kernels
+//! have their own drivers in [`lanes`].
+
+use arbitrary::Arbitrary;
+use eint::{Eint, E256, E512};
+use fast_eint::backend::{Dispatch, EintBackend};
+use fast_eint::lane::as_bytes_mut;
+use fast_eint::portable;
+
+pub mod lanes;
+
+/// Bytes around every `dst` buffer that no kernel may write.
+const GUARD: usize = 32;
+const GUARD_BYTE: u8 = 0x5a;
+
+/// Elements per call, enough to get past the SIMD thresholds.
+const MAX_LEN: usize = 64;
+
+#[derive(Arbitrary, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overlap {
+    None,
+    /// `b` is `a`
+    SameOperands,
+    /// `dst` is `a`, for kernels that work in place
+    DstIsA,
+    /// `dst` is `b`, for kernels that work in place
+    DstIsB,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Batch {
+    /// Index into [`Dispatch::ALL`]
+    pub backend: u8,
+    /// Selects between the variants of a target, e.g. 256 or 512 bits
+    pub variant: bool,
+    /// Misalignment of `a`, `b` and `dst`, modulo 8
+    pub offsets: [u8; 3],
+    pub overlap: Overlap,
+    pub shift: u32,
+    pub data: Vec<u8>,
+}
+
+impl Batch {
+    fn backend(&self) -> Dispatch {
+        Dispatch::ALL[self.backend as usize % Dispatch::ALL.len()]
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        self.offsets[i] as usize % 8
+    }
+
+    /// As many whole groups of `bytes` as `data` holds, up to [`MAX_LEN`].
+    fn len(&self, bytes: usize) -> usize {
+        (self.data.len() / bytes).min(MAX_LEN)
+    }
+}
+
+/// A byte buffer whose contents start `offset` bytes into the allocation.
+struct Buffer {
+    bytes: Vec<u8>,
+    offset: usize,
+}
+
+impl Buffer {
+    /// Ends exactly at the end of the allocation.
+    fn new(contents: &[u8], offset: usize) -> Buffer {
+        let mut bytes = vec![0u8; offset + contents.len()];
+        bytes[offset..].copy_from_slice(contents);
+        Buffer { bytes, offset }
+    }
+
+    /// `len` bytes between [`GUARD`] bytes on either side.
+    fn guarded(len: usize, offset: usize) -> Buffer {
+        Buffer {
+            bytes: vec![GUARD_BYTE; offset + GUARD + len + GUARD],
+            offset: offset + GUARD,
+        }
+    }
+
+    fn ptr(&mut self) -> *mut u8 {
+        self.bytes[self.offset..].as_mut_ptr()
+    }
+
+    fn contents(&self, len: usize) -> &[u8] {
+        &self.bytes[self.offset..self.offset + len]
+    }
+
+    fn check(&self, expected: &[u8]) {
+        let len = expected.len();
+        assert_eq!(self.contents(len), expected);
+        let before = &self.bytes[self.offset - GUARD..self.offset];
+        let after = &self.bytes[self.offset + len..];
+        assert!(before.iter().all(|&x| x == GUARD_BYTE), "write before dst");
+        assert!(after.iter().all(|&x| x == GUARD_BYTE), "write after dst");
+    }
+}
+
+/// A `(a, b, dst, len)` kernel with `width`-byte operands and `out`-byte
+/// results.
+pub struct Binary {
+    pub width: usize,
+    pub out: usize,
+    pub kernel: fn(&Dispatch, *const u8, *const u8, *mut u8, usize),
+    pub reference: fn(&[u8], &[u8], &mut [u8]),
+    /// Whether `dst` may be one of the operands. The asm multiplies write
+    /// `dst` while still reading the operands.
+    pub in_place: bool,
+}
+
+macro_rules! binary {
+    ($name:ident, $ty:ident, $width:expr, $op:ident, $in_place:expr) => {
+        Binary {
+            width: $width,
+            out: $width,
+            kernel: <Dispatch as EintBackend>::$name,
+            reference: |a, b, dst| $ty::get(a).$op($ty::get(b)).put(dst),
+            in_place: $in_place,
+        }
+    };
+}
+
+macro_rules! widening {
+    ($name:ident, $ty:ident, $width:expr) => {
+        Binary {
+            width: $width,
+            out: 2 * $width,
+            kernel: <Dispatch as EintBackend>::$name,
+            reference: |a, b, dst| {
+                let (lo, hi) = $ty::get(a).widening_mul_u($ty::get(b));
+                lo.put(&mut dst[..$width]);
+                hi.put(&mut dst[$width..]);
+            },
+            in_place: false,
+        }
+    };
+}
+
+pub const WRAPPING_ADD: [Binary; 2] = [
+    binary!(wrapping_add_256, E256, 32, wrapping_add, true),
+    binary!(wrapping_add_512, E512, 64, wrapping_add, true),
+];
+
+pub const WRAPPING_SUB: [Binary; 2] = [
+    binary!(wrapping_sub_256, E256, 32, wrapping_sub, true),
+    binary!(wrapping_sub_512, E512, 64, wrapping_sub, true),
+];
+
+pub const WRAPPING_MUL: [Binary; 2] = [
+    binary!(wrapping_mul_256, E256, 32, wrapping_mul, false),
+    binary!(wrapping_mul_512, E512, 64, wrapping_mul, false),
+];
+
+pub const WIDENING_MUL: [Binary; 2] = [
+    widening!(widening_mul_256, E256, 32),
+    widening!(widening_mul_512, E512, 64),
+];
+
+pub fn run_binary(op: &Binary, batch: &Batch) {
+    let backend = batch.backend();
+    let len = batch.len(2 * op.width);
+    let (data_a, data_b) = batch.data[..2 * op.width * len].split_at(op.width * len);
+    let data_b = match batch.overlap {
+        Overlap::SameOperands => data_a,
+        _ => data_b,
+    };
+
+    let mut expected = vec![0u8; op.out * len];
+    for i in 0..len {
+        (op.reference)(
+            &data_a[i * op.width..(i + 1) * op.width],
+            &data_b[i * op.width..(i + 1) * op.width],
+            &mut expected[i * op.out..(i + 1) * op.out],
+        );
+    }
+
+    let mut a = Buffer::new(data_a, batch.offset(0));
+    let mut b = Buffer::new(data_b, batch.offset(1));
+    let b_ptr = match batch.overlap {
+        Overlap::SameOperands => a.ptr(),
+        _ => b.ptr(),
+    };
+    match batch.overlap {
+        Overlap::DstIsA if op.in_place => {
+            (op.kernel)(&backend, a.ptr(), b_ptr, a.ptr(), len);
+            assert_eq!(a.contents(op.out * len), &expected[..]);
+        }
+        Overlap::DstIsB if op.in_place => {
+            (op.kernel)(&backend, a.ptr(), b_ptr, b.ptr(), len);
+            assert_eq!(b.contents(op.out * len), &expected[..]);
+        }
+        _ => {
+            let mut dst = Buffer::guarded(op.out * len, batch.offset(2));
+            (op.kernel)(&backend, a.ptr(), b_ptr, dst.ptr(), len);
+            dst.check(&expected);
+        }
+    }
+}
+
+/// Wrapping squares when `batch.variant` is false, widening ones otherwise.
+pub fn run_sqr(batch: &Batch) {
+    let backend = batch.backend();
+    let out = if batch.variant { 64 } else { 32 };
+    let len = batch.len(32);
+    let data_a = &batch.data[..32 * len];
+
+    let mut expected = vec![0u8; out * len];
+    for i in 0..len {
+        let a = E256::get(&data_a[i * 32..(i + 1) * 32]);
+        let (lo, hi) = a.widening_mul_u(a);
+        lo.put(&mut expected[i * out..i * out + 32]);
+        if batch.variant {
+            hi.put(&mut expected[i * out + 32..(i + 1) * out]);
+        }
+    }
+
+    let mut a = Buffer::new(data_a, batch.offset(0));
+    let mut dst = Buffer::guarded(out * len, batch.offset(2));
+    if batch.variant {
+        backend.widening_sqr_256(a.ptr(), dst.ptr(), len);
+    } else {
+        backend.wrapping_sqr_256(a.ptr(), dst.ptr(), len);
+    }
+    dst.check(&expected);
+}
+
+/// `narrowing_right_shift_512`, in place for [`Overlap::DstIsA`].
+pub fn run_shift(batch: &Batch) {
+    let backend = batch.backend();
+    let len = batch.len(64);
+    let data_src = &batch.data[..64 * len];
+
+    let mut expected = vec![0u8; 32 * len];
+    for i in 0..len {
+        E512::get(&data_src[i * 64..(i + 1) * 64])
+            .wrapping_shr(batch.shift & 511)
+            .put_lo(&mut expected[i * 32..(i + 1) * 32]);
+    }
+
+    // The C kernel reads whole u64s and needs aligned buffers
+    let aligned = backend == Dispatch::C;
+    let offset = |i| if aligned { 0 } else { batch.offset(i) };
+    let mut src = Buffer::new(data_src, offset(0));
+    if batch.overlap == Overlap::DstIsA {
+        backend.narrowing_right_shift_512(src.ptr(), src.ptr(), batch.shift, len);
+        assert_eq!(src.contents(32 * len), &expected[..]);
+    } else {
+        let mut dst = Buffer::guarded(32 * len, offset(2));
+        backend.narrowing_right_shift_512(src.ptr(), dst.ptr(), batch.shift, len);
+        dst.check(&expected);
+    }
+}
+
+/// `msbc_256` on every pair of elements.
+pub fn run_msbc(batch: &Batch) {
+    let backend = batch.backend();
+    let len = batch.len(64);
+    let (data_a, data_b) = batch.data[..64 * len].split_at(32 * len);
+    for i in 0..len {
+        let mut a = Buffer::new(&data_a[i * 32..(i + 1) * 32], batch.offset(0));
+        let mut b = Buffer::new(&data_b[i * 32..(i + 1) * 32], batch.offset(1));
+        let b_ptr = match batch.overlap {
+            Overlap::SameOperands => a.ptr(),
+            _ => b.ptr(),
+        };
+        let expected = {
+            let b = if batch.overlap == Overlap::SameOperands {
+                &data_a[i * 32..(i + 1) * 32]
+            } else {
+                &data_b[i * 32..(i + 1) * 32]
+            };
+            E256::get(&data_a[i * 32..(i + 1) * 32])
+                .overflowing_sub_u(E256::get(b))
+                .1
+        };
+        assert_eq!(backend.msbc_256(a.ptr(), b_ptr), expected);
+    }
+}
+
+type Strided = fn(*const u8, isize, *const u8, isize, *mut u8, isize, usize);
+
+/// A strided root kernel and its portable twin, with the descriptor of the
+/// matching contiguous kernel for sizes and reference.
+pub struct StridedOp {
+    pub op: &'static Binary,
+    pub kernels: [Strided; 2],
+}
+
+macro_rules! strided {
+    ($op:expr, $name:ident) => {
+        StridedOp {
+            op: &$op,
+            kernels: [fast_eint::$name, portable::$name],
+        }
+    };
+}
+
+pub const STRIDED: [StridedOp; 8] = [
+    strided!(WRAPPING_ADD[0], wrapping_add_256_strided),
+    strided!(WRAPPING_ADD[1], wrapping_add_512_strided),
+    strided!(WRAPPING_SUB[0], wrapping_sub_256_strided),
+    strided!(WRAPPING_SUB[1], wrapping_sub_512_strided),
+    strided!(WRAPPING_MUL[0], wrapping_mul_256_strided),
+    strided!(WRAPPING_MUL[1], wrapping_mul_512_strided),
+    strided!(WIDENING_MUL[0], widening_mul_256_strided),
+    strided!(WIDENING_MUL[1], widening_mul_512_strided),
+];
+
+#[derive(Arbitrary, Debug)]
+pub struct StridedBatch {
+    /// Index into [`STRIDED`]
+    pub op: u8,
+    /// The portable kernel instead of the root one
+    pub portable: bool,
+    pub len: u8,
+    /// Byte strides of `a`, `b` and `dst`. Any operand stride works,
+    /// including zero and negative ones, `dst` gets at least `out` added to
+    /// its magnitude so elements do not overwrite each other.
+    pub strides: [i8; 3],
+    pub offsets: [u8; 3],
+    pub data: Vec<u8>,
+}
+
+/// `len` elements of `width` bytes, `stride` bytes apart: the buffer size
+/// and where element 0 sits in it.
+fn span(stride: isize, width: usize, len: usize) -> (usize, usize) {
+    if len == 0 {
+        return (0, 0);
+    }
+    let reach = stride.unsigned_abs() * (len - 1);
+    (reach + width, if stride < 0 { reach } else { 0 })
+}
+
+/// Index of element `i` in a buffer laid out by [`span`].
+fn element(start: usize, stride: isize, i: usize) -> usize {
+    (start as isize + stride * i as isize) as usize
+}
+
+pub fn run_strided(batch: &StridedBatch) {
+    let strided = &STRIDED[batch.op as usize % STRIDED.len()];
+    let op = strided.op;
+    let kernel = strided.kernels[batch.portable as usize];
+    let len = batch.len as usize % (MAX_LEN + 1);
+    let a_stride = batch.strides[0] as isize;
+    let b_stride = batch.strides[1] as isize;
+    let dst_stride = match batch.strides[2] as isize {
+        d if d < 0 => d - op.out as isize,
+        d => d + op.out as isize,
+    };
+
+    // Operand bytes repeat `data`, which may be shorter than the buffers
+    let fill = |size: usize, skip: usize| -> Vec<u8> {
+        if batch.data.is_empty() {
+            return vec![0u8; size];
+        }
+        batch
+            .data
+            .iter()
+            .cycle()
+            .skip(skip)
+            .take(size)
+            .copied()
+            .collect()
+    };
+    let (a_size, a_start) = span(a_stride, op.width, len);
+    let (b_size, b_start) = span(b_stride, op.width, len);
+    let (dst_size, dst_start) = span(dst_stride, op.out, len);
+    let data_a = fill(a_size, 0);
+    let data_b = fill(b_size, a_size);
+
+    let mut expected = vec![GUARD_BYTE; dst_size];
+    for i in 0..len {
+        let a = element(a_start, a_stride, i);
+        let b = element(b_start, b_stride, i);
+        let d = element(dst_start, dst_stride, i);
+        (op.reference)(
+            &data_a[a..a + op.width],
+            &data_b[b..b + op.width],
+            &mut expected[d..d + op.out],
+        );
+    }
+
+    let mut a = Buffer::new(&data_a, batch.offsets[0] as usize % 8);
+    let mut b = Buffer::new(&data_b, batch.offsets[1] as usize % 8);
+    let mut dst = Buffer::guarded(dst_size, batch.offsets[2] as usize % 8);
+    kernel(
+        a.ptr().wrapping_add(a_start),
+        a_stride,
+        b.ptr().wrapping_add(b_start),
+        b_stride,
+        dst.ptr().wrapping_add(dst_start),
+        dst_stride,
+        len,
+    );
+    dst.check(&expected);
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct MemBatch {
+    /// Index into [`Dispatch::ALL`]
+    pub backend: u8,
+    /// Order of `dst`, `a` and `b` in `mem`, one of the six permutations
+    pub order: u8,
+    /// Unused qwords before each region, modulo 4
+    pub gaps: [u8; 3],
+    /// `b_start` is `a_start`
+    pub same_operands: bool,
+    pub data: Vec<u8>,
+}
+
+/// The byte-offset `widening_mul_256(mem, dst_start, a_start, b_start, len)`,
+/// through [`EintBackend::widening_mul_256_mem`] so that the asm backend
+/// runs the legacy function itself. `dst`, `a` and `b` sit in one 8-byte
+/// aligned `mem` in a fuzzed order and with fuzzed gaps. `mem` ends right
+/// after the last region, and every byte outside `dst` must come back
+/// unchanged.
+pub fn run_mem(batch: &MemBatch) {
+    const ORDERS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let backend = Dispatch::ALL[batch.backend as usize % Dispatch::ALL.len()];
+    let len = (batch.data.len() / 64).min(MAX_LEN);
+    // dst, a and b; b takes no room when it is a
+    let sizes = [
+        64 * len,
+        32 * len,
+        if batch.same_operands { 0 } else { 32 * len },
+    ];
+
+    let mut starts = [0usize; 3];
+    let mut end = 0;
+    for &region in &ORDERS[batch.order as usize % ORDERS.len()] {
+        end += 8 * (batch.gaps[region] as usize % 4);
+        starts[region] = end;
+        end += sizes[region];
+    }
+    let [dst_start, a_start, mut b_start] = starts;
+    if batch.same_operands {
+        b_start = a_start;
+    }
+
+    let mut qwords = vec![0u64; end.div_ceil(8)];
+    let mem = as_bytes_mut(&mut qwords);
+    mem.fill(GUARD_BYTE);
+    mem[a_start..a_start + 32 * len].copy_from_slice(&batch.data[..32 * len]);
+    if !batch.same_operands {
+        mem[b_start..b_start + 32 * len].copy_from_slice(&batch.data[32 * len..64 * len]);
+    }
+
+    let mut expected = mem.to_vec();
+    for i in 0..len {
+        let a = E256::get(&mem[a_start + 32 * i..a_start + 32 * (i + 1)]);
+        let b = E256::get(&mem[b_start + 32 * i..b_start + 32 * (i + 1)]);
+        let (lo, hi) = a.widening_mul_u(b);
+        let d = dst_start + 64 * i;
+        lo.put(&mut expected[d..d + 32]);
+        hi.put(&mut expected[d + 32..d + 64]);
+    }
+
+    backend.widening_mul_256_mem(mem, dst_start, a_start, b_start, len);
+    assert_eq!(mem, &expected[..]);
+}
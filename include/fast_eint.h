@@ -0,0 +1,114 @@
+/* Generated by build.rs, do not edit. */
+
+/*
+ * Every batch operation of the crate: the raw pointer kernels and their
+ * strided forms, the simd dispatchers (x86_64 only), the wide and ct
+ * kernels, and the modular contexts behind opaque handles.
+ *
+ * Numbers are little-endian u64 limbs. The raw pointer kernels accept
+ * any alignment. The wide, ct and modular functions view their buffers
+ * as arrays of lanes: every buffer must be 8-byte aligned and outputs
+ * must not overlap inputs. Breaking that, or passing an unsupported
+ * backend, aborts the process.
+ */
+
+#ifndef FAST_EINT_H
+#define FAST_EINT_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct fast_eint_modulus256 fast_eint_modulus256;
+typedef struct fast_eint_montgomery256 fast_eint_montgomery256;
+typedef struct fast_eint_barrett256 fast_eint_barrett256;
+
+void fast_eint_wrapping_add_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_add_256_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_add_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_add_512_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_sub_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_sub_256_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_sub_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_sub_512_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_mul_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_mul_256_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_mul_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wrapping_mul_512_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_widening_mul_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_widening_mul_256_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_widening_mul_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_widening_mul_512_strided(const uint8_t *a, ptrdiff_t a_stride, const uint8_t *b, ptrdiff_t b_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_widening_sqr_256(const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_widening_sqr_256_strided(const uint8_t *a, ptrdiff_t a_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_wrapping_sqr_256(const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_wrapping_sqr_256_strided(const uint8_t *a, ptrdiff_t a_stride, uint8_t *dst, ptrdiff_t dst_stride, size_t len);
+void fast_eint_narrowing_right_shift_512(const uint8_t *src, uint8_t *dst, uint32_t shift, size_t len);
+void fast_eint_narrowing_right_shift_512_strided(const uint8_t *src, ptrdiff_t src_stride, uint8_t *dst, ptrdiff_t dst_stride, uint32_t shift, size_t len);
+bool fast_eint_msbc_256(const uint8_t *a, const uint8_t *b);
+
+#if defined(__x86_64__) || defined(_M_X64)
+#define FAST_EINT_BACKEND_SCALAR 0
+#define FAST_EINT_BACKEND_AVX2 1
+#define FAST_EINT_BACKEND_AVX512 2
+#define FAST_EINT_BACKEND_IFMA 3
+
+void fast_eint_simd_widening_mul_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_widening_mul_256_with(uint32_t backend, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_mul_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_mul_256_with(uint32_t backend, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_add_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_add_256_with(uint32_t backend, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_sub_256(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_sub_256_with(uint32_t backend, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_add_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_simd_wrapping_add_512_with(uint32_t backend, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+#endif
+
+void fast_eint_wide_widening_mul_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_wrapping_mul_512(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_widening_mul_1024(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_wrapping_mul_1024(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_widening_mul_512_comba(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_wrapping_mul_512_comba(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_widening_mul_1024_comba(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_wrapping_mul_1024_comba(const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_wide_widening_sqr_512(const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_wide_wrapping_sqr_512(const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_ct_eq_256(const uint8_t *a, const uint8_t *b, uint64_t *dst, size_t len);
+void fast_eint_ct_lt_256(const uint8_t *a, const uint8_t *b, uint64_t *dst, size_t len);
+void fast_eint_ct_select_256(const uint64_t *mask, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_ct_swap_256(const uint64_t *mask, uint8_t *a, uint8_t *b, size_t len);
+void fast_eint_ct_wrapping_shl_256(const uint8_t *a, uint32_t shift, uint8_t *dst, size_t len);
+void fast_eint_ct_wrapping_shr_256(const uint8_t *a, uint32_t shift, uint8_t *dst, size_t len);
+void fast_eint_ct_narrowing_right_shift_512(const uint8_t *src, uint8_t *dst, uint32_t shift, size_t len);
+uint64_t fast_eint_ct_mask_nonzero(uint64_t x);
+fast_eint_modulus256 *fast_eint_modulus256_new(const uint8_t *m);
+void fast_eint_modulus256_free(fast_eint_modulus256 *ctx);
+void fast_eint_modulus256_reduce(const fast_eint_modulus256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_modulus256_addmod(const fast_eint_modulus256 *ctx, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_modulus256_submod(const fast_eint_modulus256 *ctx, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_modulus256_mulmod(const fast_eint_modulus256 *ctx, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+fast_eint_montgomery256 *fast_eint_montgomery256_new(const uint8_t *m);
+void fast_eint_montgomery256_free(fast_eint_montgomery256 *ctx);
+void fast_eint_montgomery256_to_mont(const fast_eint_montgomery256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_from_mont(const fast_eint_montgomery256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_mont_mul(const fast_eint_montgomery256 *ctx, const uint8_t *a, const uint8_t *b, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_mont_sqr(const fast_eint_montgomery256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_powmod(const fast_eint_montgomery256 *ctx, const uint8_t *a, const uint8_t *e, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_powmod_each(const fast_eint_montgomery256 *ctx, const uint8_t *a, const uint8_t *e, uint8_t *dst, size_t len);
+void fast_eint_montgomery256_invert_prime(const fast_eint_montgomery256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+bool fast_eint_montgomery256_invert(const fast_eint_montgomery256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+fast_eint_barrett256 *fast_eint_barrett256_new(const uint8_t *m);
+void fast_eint_barrett256_free(fast_eint_barrett256 *ctx);
+void fast_eint_barrett256_reduce_512(const fast_eint_barrett256 *ctx, const uint8_t *a, uint8_t *dst, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FAST_EINT_H */
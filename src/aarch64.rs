@@ -0,0 +1,84 @@
+//! The AArch64 backend: `adds`/`adcs` and `subs`/`sbcs` carry chains and
+//! `mul`/`umulh` product scanning in inline asm, generated by build.rs from
+//! the same kernel list as the x86-64 one.
+
+use core::arch::asm;
+
+// Unrolled add, sub and mul kernels, see `KERNELS` in build.rs
+include!(concat!(env!("OUT_DIR"), "/kernels_aarch64.rs"));
+
+pub fn msbc_256(a: *const u8, b: *const u8) -> bool {
+    let result: u64;
+    unsafe {
+        asm!(
+            "ldp x3, x4, [{a}]",
+            "ldp x5, x6, [{a}, #16]",
+            "ldp x7, x8, [{b}]",
+            "ldp x9, x10, [{b}, #16]",
+            "subs x3, x3, x7",
+            "sbcs x4, x4, x8",
+            "sbcs x5, x5, x9",
+            "sbcs x6, x6, x10",
+            // Carry clear means the subtraction borrowed
+            "cset {result}, lo",
+            a = in(reg) a,
+            b = in(reg) b,
+            result = out(reg) result,
+            out("x3") _,
+            out("x4") _,
+            out("x5") _,
+            out("x6") _,
+            out("x7") _,
+            out("x8") _,
+            out("x9") _,
+            out("x10") _,
+            options(nostack, readonly),
+        );
+    }
+    result != 0
+}
+
+#[inline(always)]
+pub(crate) unsafe fn widening_mul_256_impl(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    for i in 0..len {
+        unsafe {
+            widening_mul_256_one(
+                a.wrapping_add(i * 32),
+                b.wrapping_add(i * 32),
+                dst.wrapping_add(i * 64),
+            );
+        }
+    }
+}
+
+#[inline(never)]
+pub fn widening_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, a, 32, dst, 64, len);
+}
+
+#[inline(never)]
+pub fn widening_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    widening_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    wrapping_mul_256(a, a, dst, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    wrapping_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
@@ -0,0 +1,656 @@
+//! Every operation behind one trait, so an implementation is picked by value
+//! instead of by importing a differently named symbol.
+//!
+//! The backends are unit types: [`Asm`] for the crate root kernels, [`Portable`]
+//! for [`crate::portable`], `C` for the `c-reference` twins and `Simd` for
+//! the runtime-detected vector kernels in `crate::simd` on x86_64.
+//! [`Dispatch`] names one of them at runtime and implements the trait by
+//! forwarding to it.
+//!
+//! All methods take the packed operands of the contiguous root kernels, with
+//! `widening_mul_256` in its pointer form and the byte-offset form as
+//! [`EintBackend::widening_mul_256_mem`]. Methods with a default run the
+//! portable kernel or, for the wide lanes, [`crate::wide`], and a backend
+//! overrides them where it has its own.
+//!
+//! [`crate::modular`] and [`crate::ct`] are deliberately not in the trait.
+//! They are built on the crate root kernels and have one implementation
+//! each, so there is nothing to pick between. `ct` must also stay on code
+//! that was reviewed for constant time, which a backend swap would defeat.
+
+use crate::lane::{from_raw, from_raw_mut, U1024, U2048, U512};
+use crate::portable;
+#[cfg(target_arch = "x86_64")]
+use crate::simd;
+use crate::wide;
+
+macro_rules! strided_defaults {
+    ($($op:ident,)*) => {
+        $(
+            #[allow(clippy::too_many_arguments)]
+            fn $op(
+                &self,
+                a: *const u8,
+                a_stride: isize,
+                b: *const u8,
+                b_stride: isize,
+                dst: *mut u8,
+                dst_stride: isize,
+                len: usize,
+            ) {
+                portable::$op(a, a_stride, b, b_stride, dst, dst_stride, len)
+            }
+        )*
+    };
+}
+
+pub trait EintBackend {
+    /// Short lowercase name, for test failures and benchmark labels.
+    fn name(&self) -> &'static str;
+
+    fn wrapping_add_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_add_512(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_sub_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_sub_512(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_mul_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_mul_512(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn widening_mul_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn widening_mul_512(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize);
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize);
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize);
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize);
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool;
+
+    /// The byte-offset form of [`crate::widening_mul_256`], on this backend's
+    /// pointer form.
+    fn widening_mul_256_mem(
+        &self,
+        mem: &mut [u8],
+        dst_start: usize,
+        a_start: usize,
+        b_start: usize,
+        len: usize,
+    ) {
+        let base = mem.as_mut_ptr();
+        self.widening_mul_256(
+            base.wrapping_add(a_start),
+            base.wrapping_add(b_start),
+            base.wrapping_add(dst_start),
+            len,
+        )
+    }
+
+    strided_defaults!(
+        wrapping_add_256_strided,
+        wrapping_add_512_strided,
+        wrapping_sub_256_strided,
+        wrapping_sub_512_strided,
+        wrapping_mul_256_strided,
+        wrapping_mul_512_strided,
+        widening_mul_256_strided,
+        widening_mul_512_strided,
+    );
+
+    fn wrapping_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        portable::wrapping_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    fn widening_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        portable::widening_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    /// The shift is plain Rust on every target, so all backends share it.
+    fn narrowing_right_shift_512_strided(
+        &self,
+        src: *const u8,
+        src_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        shift: u32,
+        len: usize,
+    ) {
+        crate::narrowing_right_shift_512_strided(src, src_stride, dst, dst_stride, shift, len)
+    }
+
+    // The wide lanes take 8-byte aligned buffers, which they view as lanes.
+
+    fn widening_mul_1024(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::widening_mul_1024(
+                from_raw(a.cast::<U1024>(), len),
+                from_raw(b.cast(), len),
+                from_raw_mut(dst.cast::<U2048>(), len),
+            )
+        }
+    }
+
+    fn wrapping_mul_1024(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::wrapping_mul_1024(
+                from_raw(a.cast::<U1024>(), len),
+                from_raw(b.cast(), len),
+                from_raw_mut(dst.cast(), len),
+            )
+        }
+    }
+
+    fn widening_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::widening_sqr_512(
+                from_raw(a.cast::<U512>(), len),
+                from_raw_mut(dst.cast::<U1024>(), len),
+            )
+        }
+    }
+
+    fn wrapping_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::wrapping_sqr_512(
+                from_raw(a.cast::<U512>(), len),
+                from_raw_mut(dst.cast(), len),
+            )
+        }
+    }
+}
+
+macro_rules! forward {
+    ($($op:ident => $f:path,)*) => {
+        $(
+            #[inline]
+            fn $op(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+                $f(a, b, dst, len)
+            }
+        )*
+    };
+}
+
+macro_rules! forward_strided {
+    ($($op:ident => $f:path,)*) => {
+        $(
+            #[inline]
+            fn $op(
+                &self,
+                a: *const u8,
+                a_stride: isize,
+                b: *const u8,
+                b_stride: isize,
+                dst: *mut u8,
+                dst_stride: isize,
+                len: usize,
+            ) {
+                $f(a, a_stride, b, b_stride, dst, dst_stride, len)
+            }
+        )*
+    };
+}
+
+/// The crate root kernels: inline asm where the target has a backend, the
+/// portable ones elsewhere.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Asm;
+
+impl EintBackend for Asm {
+    fn name(&self) -> &'static str {
+        "asm"
+    }
+
+    forward!(
+        wrapping_add_256 => crate::wrapping_add_256,
+        wrapping_add_512 => crate::wrapping_add_512,
+        wrapping_sub_256 => crate::wrapping_sub_256,
+        wrapping_sub_512 => crate::wrapping_sub_512,
+        wrapping_mul_256 => crate::wrapping_mul_256,
+        wrapping_mul_512 => crate::wrapping_mul_512,
+        widening_mul_512 => crate::widening_mul_512,
+    );
+
+    fn widening_mul_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        crate::widening_mul_256_strided(a, 32, b, 32, dst, 64, len)
+    }
+
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        crate::wrapping_sqr_256(a, dst, len)
+    }
+
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        crate::widening_sqr_256(a, dst, len)
+    }
+
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+        crate::narrowing_right_shift_512(src, dst, shift, len)
+    }
+
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool {
+        crate::msbc_256(a, b)
+    }
+
+    fn widening_mul_256_mem(
+        &self,
+        mem: &mut [u8],
+        dst_start: usize,
+        a_start: usize,
+        b_start: usize,
+        len: usize,
+    ) {
+        crate::widening_mul_256(mem, dst_start, a_start, b_start, len)
+    }
+
+    forward_strided!(
+        wrapping_add_256_strided => crate::wrapping_add_256_strided,
+        wrapping_add_512_strided => crate::wrapping_add_512_strided,
+        wrapping_sub_256_strided => crate::wrapping_sub_256_strided,
+        wrapping_sub_512_strided => crate::wrapping_sub_512_strided,
+        wrapping_mul_256_strided => crate::wrapping_mul_256_strided,
+        wrapping_mul_512_strided => crate::wrapping_mul_512_strided,
+        widening_mul_256_strided => crate::widening_mul_256_strided,
+        widening_mul_512_strided => crate::widening_mul_512_strided,
+    );
+
+    fn wrapping_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        crate::wrapping_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    fn widening_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        crate::widening_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+}
+
+/// [`crate::portable`], on every target. The shift is plain Rust everywhere
+/// and shared with [`Asm`]. The wide lanes use the schoolbook `_comba`
+/// kernels of [`crate::wide`], which do not call into the root kernels.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Portable;
+
+impl EintBackend for Portable {
+    fn name(&self) -> &'static str {
+        "portable"
+    }
+
+    forward!(
+        wrapping_add_256 => portable::wrapping_add_256,
+        wrapping_add_512 => portable::wrapping_add_512,
+        wrapping_sub_256 => portable::wrapping_sub_256,
+        wrapping_sub_512 => portable::wrapping_sub_512,
+        wrapping_mul_256 => portable::wrapping_mul_256,
+        wrapping_mul_512 => portable::wrapping_mul_512,
+        widening_mul_512 => portable::widening_mul_512,
+    );
+
+    fn widening_mul_256(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        portable::widening_mul_256_strided(a, 32, b, 32, dst, 64, len)
+    }
+
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        portable::wrapping_sqr_256(a, dst, len)
+    }
+
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        portable::widening_sqr_256(a, dst, len)
+    }
+
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+        crate::narrowing_right_shift_512(src, dst, shift, len)
+    }
+
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool {
+        portable::msbc_256(a, b)
+    }
+
+    fn widening_mul_1024(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::widening_mul_1024_comba(
+                from_raw(a.cast::<U1024>(), len),
+                from_raw(b.cast(), len),
+                from_raw_mut(dst.cast::<U2048>(), len),
+            )
+        }
+    }
+
+    fn wrapping_mul_1024(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+        unsafe {
+            wide::wrapping_mul_1024_comba(
+                from_raw(a.cast::<U1024>(), len),
+                from_raw(b.cast(), len),
+                from_raw_mut(dst.cast(), len),
+            )
+        }
+    }
+
+    fn widening_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        let a = unsafe { from_raw(a.cast::<U512>(), len) };
+        wide::widening_mul_512_comba(a, a, unsafe { from_raw_mut(dst.cast(), len) })
+    }
+
+    fn wrapping_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        let a = unsafe { from_raw(a.cast::<U512>(), len) };
+        wide::wrapping_mul_512_comba(a, a, unsafe { from_raw_mut(dst.cast(), len) })
+    }
+}
+
+/// The C kernels from the `c-reference` feature. Like
+/// [`crate::narrowing_right_shift_512_c`], the shift needs 8-byte aligned
+/// buffers.
+#[cfg(feature = "c-reference")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct C;
+
+#[cfg(feature = "c-reference")]
+impl EintBackend for C {
+    fn name(&self) -> &'static str {
+        "c"
+    }
+
+    forward!(
+        wrapping_add_256 => crate::wrapping_add_256_c,
+        wrapping_add_512 => crate::wrapping_add_512_c,
+        wrapping_sub_256 => crate::wrapping_sub_256_c,
+        wrapping_sub_512 => crate::wrapping_sub_512_c,
+        wrapping_mul_256 => crate::wrapping_mul_256_c,
+        wrapping_mul_512 => crate::wrapping_mul_512_c,
+        widening_mul_256 => crate::widening_mul_256_c,
+        widening_mul_512 => crate::widening_mul_512_c,
+    );
+
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        crate::wrapping_sqr_256_c(a, dst, len)
+    }
+
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        crate::widening_sqr_256_c(a, dst, len)
+    }
+
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+        crate::narrowing_right_shift_512_c(src, dst, shift, len)
+    }
+
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool {
+        crate::msbc_256_c(a, b)
+    }
+}
+
+/// The [`crate::simd`] entry points, which pick a vector backend per call
+/// from the running CPU and batch size. Operations without vector kernels,
+/// including every strided one, run on [`Asm`].
+#[cfg(target_arch = "x86_64")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Simd;
+
+#[cfg(target_arch = "x86_64")]
+impl EintBackend for Simd {
+    fn name(&self) -> &'static str {
+        "simd"
+    }
+
+    forward!(
+        wrapping_add_256 => simd::wrapping_add_256,
+        wrapping_add_512 => simd::wrapping_add_512,
+        wrapping_sub_256 => simd::wrapping_sub_256,
+        wrapping_sub_512 => crate::wrapping_sub_512,
+        wrapping_mul_256 => simd::wrapping_mul_256,
+        wrapping_mul_512 => crate::wrapping_mul_512,
+        widening_mul_256 => simd::widening_mul_256,
+        widening_mul_512 => crate::widening_mul_512,
+    );
+
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        simd::wrapping_mul_256(a, a, dst, len)
+    }
+
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        simd::widening_mul_256(a, a, dst, len)
+    }
+
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+        crate::narrowing_right_shift_512(src, dst, shift, len)
+    }
+
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool {
+        crate::msbc_256(a, b)
+    }
+
+    forward_strided!(
+        wrapping_add_256_strided => crate::wrapping_add_256_strided,
+        wrapping_add_512_strided => crate::wrapping_add_512_strided,
+        wrapping_sub_256_strided => crate::wrapping_sub_256_strided,
+        wrapping_sub_512_strided => crate::wrapping_sub_512_strided,
+        wrapping_mul_256_strided => crate::wrapping_mul_256_strided,
+        wrapping_mul_512_strided => crate::wrapping_mul_512_strided,
+        widening_mul_256_strided => crate::widening_mul_256_strided,
+        widening_mul_512_strided => crate::widening_mul_512_strided,
+    );
+
+    fn wrapping_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        crate::wrapping_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    fn widening_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        crate::widening_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+}
+
+/// A backend chosen at runtime, e.g. from configuration. The default is the
+/// fastest one for large batches on this target.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dispatch {
+    Asm,
+    Portable,
+    #[cfg(feature = "c-reference")]
+    C,
+    #[cfg(target_arch = "x86_64")]
+    Simd,
+}
+
+impl Dispatch {
+    /// Every backend built into this crate.
+    pub const ALL: &'static [Dispatch] = &[
+        Dispatch::Asm,
+        Dispatch::Portable,
+        #[cfg(feature = "c-reference")]
+        Dispatch::C,
+        #[cfg(target_arch = "x86_64")]
+        Dispatch::Simd,
+    ];
+
+    /// Looks a backend up by its [`EintBackend::name`].
+    pub fn from_name(name: &str) -> Option<Dispatch> {
+        Dispatch::ALL.iter().copied().find(|d| d.name() == name)
+    }
+
+    fn backend(self) -> &'static dyn EintBackend {
+        match self {
+            Dispatch::Asm => &Asm,
+            Dispatch::Portable => &Portable,
+            #[cfg(feature = "c-reference")]
+            Dispatch::C => &C,
+            #[cfg(target_arch = "x86_64")]
+            Dispatch::Simd => &Simd,
+        }
+    }
+}
+
+impl Default for Dispatch {
+    fn default() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        return Dispatch::Simd;
+        #[cfg(not(target_arch = "x86_64"))]
+        return Dispatch::Asm;
+    }
+}
+
+macro_rules! dispatch {
+    ($($op:ident,)*) => {
+        $(
+            #[inline]
+            fn $op(&self, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+                self.backend().$op(a, b, dst, len)
+            }
+        )*
+    };
+}
+
+macro_rules! dispatch_strided {
+    ($($op:ident,)*) => {
+        $(
+            #[inline]
+            fn $op(
+                &self,
+                a: *const u8,
+                a_stride: isize,
+                b: *const u8,
+                b_stride: isize,
+                dst: *mut u8,
+                dst_stride: isize,
+                len: usize,
+            ) {
+                self.backend()
+                    .$op(a, a_stride, b, b_stride, dst, dst_stride, len)
+            }
+        )*
+    };
+}
+
+impl EintBackend for Dispatch {
+    fn name(&self) -> &'static str {
+        self.backend().name()
+    }
+
+    dispatch!(
+        wrapping_add_256,
+        wrapping_add_512,
+        wrapping_sub_256,
+        wrapping_sub_512,
+        wrapping_mul_256,
+        wrapping_mul_512,
+        widening_mul_256,
+        widening_mul_512,
+    );
+
+    fn wrapping_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        self.backend().wrapping_sqr_256(a, dst, len)
+    }
+
+    fn widening_sqr_256(&self, a: *const u8, dst: *mut u8, len: usize) {
+        self.backend().widening_sqr_256(a, dst, len)
+    }
+
+    fn narrowing_right_shift_512(&self, src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+        self.backend()
+            .narrowing_right_shift_512(src, dst, shift, len)
+    }
+
+    fn msbc_256(&self, a: *const u8, b: *const u8) -> bool {
+        self.backend().msbc_256(a, b)
+    }
+
+    fn widening_mul_256_mem(
+        &self,
+        mem: &mut [u8],
+        dst_start: usize,
+        a_start: usize,
+        b_start: usize,
+        len: usize,
+    ) {
+        self.backend()
+            .widening_mul_256_mem(mem, dst_start, a_start, b_start, len)
+    }
+
+    dispatch_strided!(
+        wrapping_add_256_strided,
+        wrapping_add_512_strided,
+        wrapping_sub_256_strided,
+        wrapping_sub_512_strided,
+        wrapping_mul_256_strided,
+        wrapping_mul_512_strided,
+        widening_mul_256_strided,
+        widening_mul_512_strided,
+    );
+
+    fn wrapping_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        self.backend()
+            .wrapping_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    fn widening_sqr_256_strided(
+        &self,
+        a: *const u8,
+        a_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        len: usize,
+    ) {
+        self.backend()
+            .widening_sqr_256_strided(a, a_stride, dst, dst_stride, len)
+    }
+
+    fn narrowing_right_shift_512_strided(
+        &self,
+        src: *const u8,
+        src_stride: isize,
+        dst: *mut u8,
+        dst_stride: isize,
+        shift: u32,
+        len: usize,
+    ) {
+        self.backend()
+            .narrowing_right_shift_512_strided(src, src_stride, dst, dst_stride, shift, len)
+    }
+
+    dispatch!(widening_mul_1024, wrapping_mul_1024,);
+
+    fn widening_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        self.backend().widening_sqr_512(a, dst, len)
+    }
+
+    fn wrapping_sqr_512(&self, a: *const u8, dst: *mut u8, len: usize) {
+        self.backend().wrapping_sqr_512(a, dst, len)
+    }
+}
@@ -0,0 +1,57 @@
+//! C ABI for every batch operation, enabled by the `capi` feature.
+//!
+//! Everything is exported with a `fast_eint_` prefix: the contiguous and
+//! strided raw pointer kernels, the [`crate::simd`] dispatchers and their
+//! `_with` forms on x86_64, the [`crate::wide`] and [`crate::ct`] kernels,
+//! and the [`crate::modular`] contexts as opaque heap handles with `_new`
+//! and `_free`. The exports and `include/fast_eint.h` are both generated by
+//! build.rs from one list, and a test keeps the checked-in header in sync.
+//! Build the libraries with
+//!
+//! ```sh
+//! cargo rustc --release --lib --features capi --crate-type staticlib
+//! cargo rustc --release --lib --features capi --crate-type cdylib
+//! ```
+//!
+//! `make -C tests/c` does both and runs the C tests against each.
+//!
+//! The slice-based kernels are reached through views of the C buffers, so
+//! unlike the raw pointer kernels they need 8-byte aligned buffers and
+//! outputs that do not overlap inputs. Misalignment and unknown backends
+//! panic, which aborts at the `extern "C"` boundary.
+
+use crate::lane::{from_raw as lanes, from_raw_mut as lanes_mut, Lane};
+use crate::modular::{Barrett256, Modulus256, Montgomery256};
+#[cfg(target_arch = "x86_64")]
+use crate::simd::Backend;
+
+/// One lane at `p`, which may be unaligned.
+unsafe fn lane<T: Lane>(p: *const u8) -> T {
+    p.cast::<T>().read_unaligned()
+}
+
+/// Moves a context to the heap, or returns null for an invalid modulus.
+fn context_new<T>(ctx: Option<T>) -> *mut T {
+    ctx.map_or(std::ptr::null_mut(), |ctx| Box::into_raw(Box::new(ctx)))
+}
+
+/// Frees a context from [`context_new`]. Null is ignored, like `free`.
+unsafe fn context_free<T>(ctx: *mut T) {
+    if !ctx.is_null() {
+        drop(Box::from_raw(ctx));
+    }
+}
+
+/// The `FAST_EINT_BACKEND_*` constants of the header.
+#[cfg(target_arch = "x86_64")]
+fn backend_from_c(backend: u32) -> Backend {
+    match backend {
+        0 => Backend::Scalar,
+        1 => Backend::Avx2,
+        2 => Backend::Avx512,
+        3 => Backend::Ifma,
+        _ => panic!("fast_eint: unknown backend {}", backend),
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/capi.rs"));
@@ -0,0 +1,211 @@
+//! Constant-time kernels for secret-dependent data.
+//!
+//! Timing of the rest of the crate, for reference:
+//!
+//! * The asm `wrapping_add/sub/mul` and `widening_mul_256` kernels and
+//!   [`crate::msbc_256`] are straight-line code whose memory accesses depend
+//!   only on the pointers and `len`.
+//! * [`crate::narrowing_right_shift_512`] branches on the shift and picks
+//!   which source limbs to load from it, so the shift leaks. Use
+//!   [`narrowing_right_shift_512`] here when it is secret.
+//! * The `simd` dispatchers pick a backend from the CPU and `len` only.
+//! * In `modular`, `Montgomery256` multiplication, `powmod` and `invert` do
+//!   not branch on or index by their operands. `Modulus256` and `Barrett256`
+//!   use data-dependent division and correction loops and are not
+//!   constant-time.
+//!
+//! Masks are `u64` values of either all ones (true) or zero (false). The
+//! code below only combines them with bitwise operations. Every mask passes
+//! through `barrier` where it is made, so the optimizer cannot see that it
+//! only takes two values and turn the selects back into branches.
+//!
+//! The barrier is an empty inline asm block that takes the mask in a
+//! register and hands it back. The compiler has to assume the asm may
+//! return any value, so it knows nothing about the mask past that point.
+//! Other targets, where a `u64` may not fit one register, use a volatile
+//! read of a stack slot instead, which `subtle` also relies on. Neither
+//! stops the CPU itself from taking data-dependent time, which only the
+//! instruction choice can avoid.
+
+use crate::lane::{U256, U512};
+use crate::limb;
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "loongarch64"
+))]
+use core::arch::asm;
+
+/// Returns `x` unchanged, opaque to the optimizer.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "loongarch64"
+))]
+#[inline(always)]
+fn barrier(mut x: u64) -> u64 {
+    unsafe {
+        asm!("/* {0} */", inout(reg) x, options(pure, nomem, nostack, preserves_flags));
+    }
+    x
+}
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "loongarch64"
+)))]
+#[inline(always)]
+fn barrier(x: u64) -> u64 {
+    unsafe { core::ptr::read_volatile(&x) }
+}
+
+/// All ones if `x` is non-zero.
+#[inline(always)]
+pub fn mask_nonzero(x: u64) -> u64 {
+    barrier(((x | x.wrapping_neg()) >> 63).wrapping_neg())
+}
+
+/// All ones if the low bit `b` is set, for `b` of 0 or 1 such as a carry.
+#[inline(always)]
+pub(crate) fn mask_bit(b: u64) -> u64 {
+    barrier(b.wrapping_neg())
+}
+
+/// Copies `src` into `dst` where `mask` is all ones, leaves `dst` alone
+/// where it is zero.
+#[inline(always)]
+pub(crate) fn select(mask: u64, dst: &mut [u64], src: &[u64]) {
+    for (d, s) in dst.iter_mut().zip(src.iter()) {
+        *d ^= mask & (*d ^ *s);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn cswap(mask: u64, a: &mut [u64], b: &mut [u64]) {
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let t = mask & (*x ^ *y);
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+/// Writes `a[i] == b[i]` masks into `dst`.
+pub fn eq_256(a: &[U256], b: &[U256], dst: &mut [u64]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    for i in 0..dst.len() {
+        let diff = a[i]
+            .0
+            .iter()
+            .zip(b[i].0.iter())
+            .fold(0, |acc, (x, y)| acc | (x ^ y));
+        dst[i] = !mask_nonzero(diff);
+    }
+}
+
+/// Writes unsigned `a[i] < b[i]` masks into `dst`.
+pub fn lt_256(a: &[U256], b: &[U256], dst: &mut [u64]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    for i in 0..dst.len() {
+        let mut t = a[i];
+        let borrow = limb::sub_assign(&mut t.0, &b[i].0);
+        dst[i] = mask_bit(borrow);
+    }
+}
+
+/// Writes `mask[i] ? a[i] : b[i]` into `dst`.
+pub fn select_256(mask: &[u64], a: &[U256], b: &[U256], dst: &mut [U256]) {
+    assert!(mask.len() == dst.len() && a.len() == dst.len() && b.len() == dst.len());
+    for i in 0..dst.len() {
+        dst[i] = b[i];
+        select(mask[i], &mut dst[i].0, &a[i].0);
+    }
+}
+
+/// Swaps `a[i]` and `b[i]` where `mask[i]` is set.
+pub fn swap_256(mask: &[u64], a: &mut [U256], b: &mut [U256]) {
+    assert!(mask.len() == a.len() && mask.len() == b.len());
+    for i in 0..mask.len() {
+        cswap(mask[i], &mut a[i].0, &mut b[i].0);
+    }
+}
+
+/// Shifts left by a secret `shift`, which is taken modulo 256.
+pub fn wrapping_shl_256(a: &[U256], shift: u32, dst: &mut [U256]) {
+    assert!(a.len() == dst.len());
+    for (x, d) in a.iter().zip(dst.iter_mut()) {
+        shl(&x.0, shift & 255, &mut d.0);
+    }
+}
+
+/// Shifts right by a secret `shift`, which is taken modulo 256.
+pub fn wrapping_shr_256(a: &[U256], shift: u32, dst: &mut [U256]) {
+    assert!(a.len() == dst.len());
+    for (x, d) in a.iter().zip(dst.iter_mut()) {
+        shr(&x.0, shift & 255, &mut d.0);
+    }
+}
+
+/// Same result as [`crate::narrowing_right_shift_512`], without the shift
+/// leaking through branches or load addresses.
+pub fn narrowing_right_shift_512(src: &[U512], dst: &mut [U256], shift: u32) {
+    assert!(src.len() == dst.len());
+    for (x, d) in src.iter().zip(dst.iter_mut()) {
+        shr(&x.0, shift & 511, &mut d.0);
+    }
+}
+
+/// Barrel shifter: whole-limb moves are applied as masked selects for each
+/// bit of the limb count, then the sub-limb part uses the variable-distance
+/// shift instructions, which run in constant time. `(hi << 1) << (63 - s)`
+/// stands in for `hi << (64 - s)` so `s == 0` needs no special case.
+#[inline(always)]
+fn shr(src: &[u64], shift: u32, dst: &mut [u64]) {
+    let n = src.len();
+    let mut x = [0u64; 8];
+    x[..n].copy_from_slice(src);
+
+    let mut step = 1;
+    while step < n {
+        let mask = mask_nonzero(shift as u64 & (64 * step) as u64);
+        for i in 0..n {
+            let moved = if i + step < n { x[i + step] } else { 0 };
+            x[i] ^= mask & (x[i] ^ moved);
+        }
+        step <<= 1;
+    }
+
+    let bits = shift % 64;
+    for i in 0..n {
+        let hi = if i + 1 < n { x[i + 1] } else { 0 };
+        x[i] = (x[i] >> bits) | ((hi << 1) << (63 - bits));
+    }
+    dst.copy_from_slice(&x[..dst.len()]);
+}
+
+#[inline(always)]
+fn shl(src: &[u64], shift: u32, dst: &mut [u64]) {
+    let n = src.len();
+    let mut x = [0u64; 8];
+    x[..n].copy_from_slice(src);
+
+    let mut step = 1;
+    while step < n {
+        let mask = mask_nonzero(shift as u64 & (64 * step) as u64);
+        for i in (0..n).rev() {
+            let moved = if i >= step { x[i - step] } else { 0 };
+            x[i] ^= mask & (x[i] ^ moved);
+        }
+        step <<= 1;
+    }
+
+    let bits = shift % 64;
+    for i in (0..n).rev() {
+        let lo = if i > 0 { x[i - 1] } else { 0 };
+        x[i] = (x[i] << bits) | ((lo >> 1) >> (63 - bits));
+    }
+    dst.copy_from_slice(&x[..dst.len()]);
+}
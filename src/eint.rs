@@ -0,0 +1,118 @@
+//! Batch operations directly on `eint` values, enabled by the `eint` feature.
+//!
+//! `E256` through `E2048` are single-field newtypes over `[u64; N]`. Once their
+//! size and alignment match the field, the field must sit at offset 0, so
+//! slices of them share the layout of the matching lane types and can be
+//! reinterpreted without copying.
+
+use crate::lane::{self, U1024, U2048, U256, U512};
+use crate::wide;
+use ::eint::{E1024, E2048, E256, E512};
+use core::mem::{align_of, size_of};
+use core::slice;
+
+const _: () = assert!(size_of::<E256>() == size_of::<U256>());
+const _: () = assert!(align_of::<E256>() == align_of::<U256>());
+const _: () = assert!(size_of::<E512>() == size_of::<U512>());
+const _: () = assert!(align_of::<E512>() == align_of::<U512>());
+const _: () = assert!(size_of::<E1024>() == size_of::<U1024>());
+const _: () = assert!(align_of::<E1024>() == align_of::<U1024>());
+const _: () = assert!(size_of::<E2048>() == size_of::<U2048>());
+const _: () = assert!(align_of::<E2048>() == align_of::<U2048>());
+
+macro_rules! convert {
+    ($eint:ident, $lane:ident, $as_lane:ident, $as_lane_mut:ident, $as_eint:ident, $as_eint_mut:ident) => {
+        impl From<$eint> for $lane {
+            fn from(value: $eint) -> Self {
+                Self(value.0)
+            }
+        }
+
+        impl From<$lane> for $eint {
+            fn from(value: $lane) -> Self {
+                Self(value.0)
+            }
+        }
+
+        pub fn $as_lane(values: &[$eint]) -> &[$lane] {
+            unsafe { slice::from_raw_parts(values.as_ptr() as *const $lane, values.len()) }
+        }
+
+        pub fn $as_lane_mut(values: &mut [$eint]) -> &mut [$lane] {
+            unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut $lane, values.len()) }
+        }
+
+        pub fn $as_eint(lanes: &[$lane]) -> &[$eint] {
+            unsafe { slice::from_raw_parts(lanes.as_ptr() as *const $eint, lanes.len()) }
+        }
+
+        pub fn $as_eint_mut(lanes: &mut [$lane]) -> &mut [$eint] {
+            unsafe { slice::from_raw_parts_mut(lanes.as_mut_ptr() as *mut $eint, lanes.len()) }
+        }
+    };
+}
+
+convert!(E256, U256, as_u256, as_u256_mut, as_e256, as_e256_mut);
+convert!(E512, U512, as_u512, as_u512_mut, as_e512, as_e512_mut);
+convert!(E1024, U1024, as_u1024, as_u1024_mut, as_e1024, as_e1024_mut);
+convert!(E2048, U2048, as_u2048, as_u2048_mut, as_e2048, as_e2048_mut);
+
+pub fn msbc_256(a: &E256, b: &E256) -> bool {
+    lane::msbc_256(&U256(a.0), &U256(b.0))
+}
+
+pub fn wrapping_add_256(a: &[E256], b: &[E256], dst: &mut [E256]) {
+    lane::wrapping_add_256(as_u256(a), as_u256(b), as_u256_mut(dst));
+}
+
+pub fn wrapping_sub_256(a: &[E256], b: &[E256], dst: &mut [E256]) {
+    lane::wrapping_sub_256(as_u256(a), as_u256(b), as_u256_mut(dst));
+}
+
+pub fn wrapping_add_512(a: &[E512], b: &[E512], dst: &mut [E512]) {
+    lane::wrapping_add_512(as_u512(a), as_u512(b), as_u512_mut(dst));
+}
+
+pub fn wrapping_mul_256(a: &[E256], b: &[E256], dst: &mut [E256]) {
+    lane::wrapping_mul_256(as_u256(a), as_u256(b), as_u256_mut(dst));
+}
+
+pub fn widening_mul_256(a: &[E256], b: &[E256], dst: &mut [E512]) {
+    lane::widening_mul_256(as_u256(a), as_u256(b), as_u512_mut(dst));
+}
+
+pub fn wrapping_sqr_256(a: &[E256], dst: &mut [E256]) {
+    lane::wrapping_sqr_256(as_u256(a), as_u256_mut(dst));
+}
+
+pub fn widening_sqr_256(a: &[E256], dst: &mut [E512]) {
+    lane::widening_sqr_256(as_u256(a), as_u512_mut(dst));
+}
+
+pub fn narrowing_right_shift_512(src: &[E512], dst: &mut [E256], shift: u32) {
+    lane::narrowing_right_shift_512(as_u512(src), as_u256_mut(dst), shift);
+}
+
+pub fn wrapping_mul_512(a: &[E512], b: &[E512], dst: &mut [E512]) {
+    wide::wrapping_mul_512(as_u512(a), as_u512(b), as_u512_mut(dst));
+}
+
+pub fn widening_mul_512(a: &[E512], b: &[E512], dst: &mut [E1024]) {
+    wide::widening_mul_512(as_u512(a), as_u512(b), as_u1024_mut(dst));
+}
+
+pub fn wrapping_mul_1024(a: &[E1024], b: &[E1024], dst: &mut [E1024]) {
+    wide::wrapping_mul_1024(as_u1024(a), as_u1024(b), as_u1024_mut(dst));
+}
+
+pub fn widening_mul_1024(a: &[E1024], b: &[E1024], dst: &mut [E2048]) {
+    wide::widening_mul_1024(as_u1024(a), as_u1024(b), as_u2048_mut(dst));
+}
+
+pub fn wrapping_sqr_512(a: &[E512], dst: &mut [E512]) {
+    wide::wrapping_sqr_512(as_u512(a), as_u512_mut(dst));
+}
+
+pub fn widening_sqr_512(a: &[E512], dst: &mut [E1024]) {
+    wide::widening_sqr_512(as_u512(a), as_u1024_mut(dst));
+}
@@ -0,0 +1,140 @@
+//! Kernels parameterized over the byte order of each lane.
+//!
+//! Little-endian lanes go straight to the regular kernels. Big-endian lanes
+//! are byte-swapped limb by limb while being loaded into a small stack
+//! buffer, processed there, and swapped back on store, so no separate pass
+//! over the whole vector is needed.
+
+use crate::lane::{self, Lane, U256, U512};
+use core::ptr;
+
+pub trait ByteOrder {
+    const IS_LITTLE: bool;
+
+    /// Reads one lane from a possibly unaligned address.
+    ///
+    /// # Safety
+    ///
+    /// `src` must be valid for reading `T::BYTES` bytes.
+    unsafe fn load<T: Lane>(src: *const u8) -> T;
+
+    /// Writes one lane to a possibly unaligned address.
+    ///
+    /// # Safety
+    ///
+    /// `dst` must be valid for writing `T::BYTES` bytes.
+    unsafe fn store<T: Lane>(dst: *mut u8, value: &T);
+}
+
+pub enum LittleEndian {}
+
+pub enum BigEndian {}
+
+impl ByteOrder for LittleEndian {
+    const IS_LITTLE: bool = true;
+
+    unsafe fn load<T: Lane>(src: *const u8) -> T {
+        let mut value = T::default();
+        for (i, limb) in value.limbs_mut().iter_mut().enumerate() {
+            *limb = u64::from_le(ptr::read_unaligned((src as *const u64).add(i)));
+        }
+        value
+    }
+
+    unsafe fn store<T: Lane>(dst: *mut u8, value: &T) {
+        for (i, limb) in value.limbs().iter().enumerate() {
+            ptr::write_unaligned((dst as *mut u64).add(i), limb.to_le());
+        }
+    }
+}
+
+impl ByteOrder for BigEndian {
+    const IS_LITTLE: bool = false;
+
+    unsafe fn load<T: Lane>(src: *const u8) -> T {
+        let mut value = T::default();
+        for (i, limb) in value.limbs_mut().iter_mut().enumerate() {
+            *limb = u64::from_be(ptr::read_unaligned(
+                (src as *const u64).add(T::LIMBS - 1 - i),
+            ));
+        }
+        value
+    }
+
+    unsafe fn store<T: Lane>(dst: *mut u8, value: &T) {
+        for (i, limb) in value.limbs().iter().enumerate() {
+            ptr::write_unaligned((dst as *mut u64).add(T::LIMBS - 1 - i), limb.to_be());
+        }
+    }
+}
+
+const CHUNK: usize = 16;
+
+fn binary<O: ByteOrder, A: Lane, D: Lane>(
+    a: *const u8,
+    b: *const u8,
+    dst: *mut u8,
+    len: usize,
+    kernel: fn(&[A], &[A], &mut [D]),
+) {
+    let mut lanes_a = [A::default(); CHUNK];
+    let mut lanes_b = [A::default(); CHUNK];
+    let mut lanes_dst = [D::default(); CHUNK];
+
+    for start in (0..len).step_by(CHUNK) {
+        let n = core::cmp::min(CHUNK, len - start);
+        for i in 0..n {
+            unsafe {
+                lanes_a[i] = O::load(a.wrapping_add((start + i) * A::BYTES));
+                lanes_b[i] = O::load(b.wrapping_add((start + i) * A::BYTES));
+            }
+        }
+        kernel(&lanes_a[..n], &lanes_b[..n], &mut lanes_dst[..n]);
+        for (i, value) in lanes_dst[..n].iter().enumerate() {
+            unsafe { O::store(dst.wrapping_add((start + i) * D::BYTES), value) };
+        }
+    }
+}
+
+pub fn msbc_256<O: ByteOrder>(a: *const u8, b: *const u8) -> bool {
+    if O::IS_LITTLE {
+        return crate::msbc_256(a, b);
+    }
+    let (a, b): (U256, U256) = unsafe { (O::load(a), O::load(b)) };
+    lane::msbc_256(&a, &b)
+}
+
+pub fn wrapping_add_256<O: ByteOrder>(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    if O::IS_LITTLE {
+        return crate::wrapping_add_256(a, b, dst, len);
+    }
+    binary::<O, U256, U256>(a, b, dst, len, lane::wrapping_add_256);
+}
+
+pub fn wrapping_sub_256<O: ByteOrder>(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    if O::IS_LITTLE {
+        return crate::wrapping_sub_256(a, b, dst, len);
+    }
+    binary::<O, U256, U256>(a, b, dst, len, lane::wrapping_sub_256);
+}
+
+pub fn wrapping_add_512<O: ByteOrder>(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    if O::IS_LITTLE {
+        return crate::wrapping_add_512(a, b, dst, len);
+    }
+    binary::<O, U512, U512>(a, b, dst, len, lane::wrapping_add_512);
+}
+
+pub fn wrapping_mul_256<O: ByteOrder>(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    if O::IS_LITTLE {
+        return crate::wrapping_mul_256(a, b, dst, len);
+    }
+    binary::<O, U256, U256>(a, b, dst, len, lane::wrapping_mul_256);
+}
+
+pub fn widening_mul_256<O: ByteOrder>(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    if O::IS_LITTLE {
+        return crate::widening_mul_256_strided(a, 32, b, 32, dst, 64, len);
+    }
+    binary::<O, U256, U512>(a, b, dst, len, lane::widening_mul_256);
+}
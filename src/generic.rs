@@ -0,0 +1,159 @@
+//! Portable kernels for any width that is a multiple of 64 bits.
+//!
+//! [`Limbs<N>`] is a lane type with a const limb count, so each width gets
+//! its own monomorphized, fully unrolled copy of the kernels. The `_dyn`
+//! variants take the limb count at runtime instead and work on flat `u64`
+//! slices holding `len * limbs` limbs, for element widths only known once
+//! the VM runs; [`crate::lane::cast_slice`] views VM memory as `&[u64]`.
+//!
+//! None of these are constant-time, shifts in particular branch on the
+//! shift amount.
+
+use crate::lane::Lane;
+use crate::limb;
+use core::cmp::Ordering;
+
+#[repr(C, align(8))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Limbs<const N: usize>(pub [u64; N]);
+
+unsafe impl<const N: usize> Lane for Limbs<N> {
+    const LIMBS: usize = N;
+}
+
+impl<const N: usize> Limbs<N> {
+    pub const ZERO: Self = Self([0; N]);
+}
+
+impl<const N: usize> Default for Limbs<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> From<[u64; N]> for Limbs<N> {
+    fn from(limbs: [u64; N]) -> Self {
+        Self(limbs)
+    }
+}
+
+fn flat<T: Lane>(lanes: &[T]) -> &[u64] {
+    unsafe { core::slice::from_raw_parts(lanes.as_ptr() as *const u64, lanes.len() * T::LIMBS) }
+}
+
+fn flat_mut<T: Lane>(lanes: &mut [T]) -> &mut [u64] {
+    unsafe {
+        core::slice::from_raw_parts_mut(lanes.as_mut_ptr() as *mut u64, lanes.len() * T::LIMBS)
+    }
+}
+
+pub fn wrapping_add<const N: usize>(a: &[Limbs<N>], b: &[Limbs<N>], dst: &mut [Limbs<N>]) {
+    wrapping_add_dyn(flat(a), flat(b), flat_mut(dst), N);
+}
+
+pub fn wrapping_sub<const N: usize>(a: &[Limbs<N>], b: &[Limbs<N>], dst: &mut [Limbs<N>]) {
+    wrapping_sub_dyn(flat(a), flat(b), flat_mut(dst), N);
+}
+
+pub fn wrapping_mul<const N: usize>(a: &[Limbs<N>], b: &[Limbs<N>], dst: &mut [Limbs<N>]) {
+    wrapping_mul_dyn(flat(a), flat(b), flat_mut(dst), N);
+}
+
+/// `M` must be `2 * N`, which stable Rust cannot spell in the signature.
+pub fn widening_mul<const N: usize, const M: usize>(
+    a: &[Limbs<N>],
+    b: &[Limbs<N>],
+    dst: &mut [Limbs<M>],
+) {
+    const { assert!(M == 2 * N) };
+    widening_mul_dyn(flat(a), flat(b), flat_mut(dst), N);
+}
+
+pub fn cmp<const N: usize>(a: &[Limbs<N>], b: &[Limbs<N>], dst: &mut [Ordering]) {
+    cmp_dyn(flat(a), flat(b), dst, N);
+}
+
+/// Shifts left by `shift` modulo the lane width.
+pub fn wrapping_shl<const N: usize>(a: &[Limbs<N>], shift: u32, dst: &mut [Limbs<N>]) {
+    wrapping_shl_dyn(flat(a), shift, flat_mut(dst), N);
+}
+
+/// Shifts right by `shift` modulo the lane width.
+pub fn wrapping_shr<const N: usize>(a: &[Limbs<N>], shift: u32, dst: &mut [Limbs<N>]) {
+    wrapping_shr_dyn(flat(a), shift, flat_mut(dst), N);
+}
+
+fn check(a: &[u64], b: &[u64], dst: &[u64], limbs: usize) {
+    assert!(limbs > 0 && dst.len().is_multiple_of(limbs));
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+}
+
+#[inline(always)]
+pub fn wrapping_add_dyn(a: &[u64], b: &[u64], dst: &mut [u64], limbs: usize) {
+    check(a, b, dst, limbs);
+    dst.copy_from_slice(a);
+    for (d, b) in dst.chunks_exact_mut(limbs).zip(b.chunks_exact(limbs)) {
+        limb::add_assign(d, b);
+    }
+}
+
+#[inline(always)]
+pub fn wrapping_sub_dyn(a: &[u64], b: &[u64], dst: &mut [u64], limbs: usize) {
+    check(a, b, dst, limbs);
+    dst.copy_from_slice(a);
+    for (d, b) in dst.chunks_exact_mut(limbs).zip(b.chunks_exact(limbs)) {
+        limb::sub_assign(d, b);
+    }
+}
+
+#[inline(always)]
+pub fn wrapping_mul_dyn(a: &[u64], b: &[u64], dst: &mut [u64], limbs: usize) {
+    check(a, b, dst, limbs);
+    let elements = a.chunks_exact(limbs).zip(b.chunks_exact(limbs));
+    for ((a, b), d) in elements.zip(dst.chunks_exact_mut(limbs)) {
+        limb::comba(a, b, d);
+    }
+}
+
+/// `dst` holds `2 * limbs` limbs per element.
+#[inline(always)]
+pub fn widening_mul_dyn(a: &[u64], b: &[u64], dst: &mut [u64], limbs: usize) {
+    assert!(limbs > 0 && a.len().is_multiple_of(limbs));
+    assert!(a.len() == b.len() && dst.len() == 2 * a.len());
+    let elements = a.chunks_exact(limbs).zip(b.chunks_exact(limbs));
+    for ((a, b), d) in elements.zip(dst.chunks_exact_mut(2 * limbs)) {
+        limb::comba(a, b, d);
+    }
+}
+
+/// `dst` holds one [`Ordering`] per element.
+#[inline(always)]
+pub fn cmp_dyn(a: &[u64], b: &[u64], dst: &mut [Ordering], limbs: usize) {
+    assert!(limbs > 0 && a.len() == b.len() && a.len() == dst.len() * limbs);
+    let elements = a.chunks_exact(limbs).zip(b.chunks_exact(limbs));
+    for ((a, b), d) in elements.zip(dst.iter_mut()) {
+        *d = limb::cmp(a, b);
+    }
+}
+
+#[inline(always)]
+pub fn wrapping_shl_dyn(a: &[u64], shift: u32, dst: &mut [u64], limbs: usize) {
+    check(a, a, dst, limbs);
+    let shift = shift as usize % (64 * limbs);
+    let (whole, bits) = (shift / 64, (shift % 64) as u32);
+    for (a, d) in a.chunks_exact(limbs).zip(dst.chunks_exact_mut(limbs)) {
+        d[..whole].fill(0);
+        limb::shl(&a[..limbs - whole], bits, &mut d[whole..]);
+    }
+}
+
+#[inline(always)]
+pub fn wrapping_shr_dyn(a: &[u64], shift: u32, dst: &mut [u64], limbs: usize) {
+    check(a, a, dst, limbs);
+    let shift = shift as usize % (64 * limbs);
+    let (whole, bits) = (shift / 64, (shift % 64) as u32);
+    for (a, d) in a.chunks_exact(limbs).zip(dst.chunks_exact_mut(limbs)) {
+        limb::shr(&a[whole..], bits, &mut d[..limbs - whole]);
+        d[limbs - whole..].fill(0);
+    }
+}
@@ -0,0 +1,207 @@
+//! Typed lane containers and zero-copy views over byte buffers.
+//!
+//! Each lane type is a fixed number of little-endian `u64` limbs with the
+//! same layout the raw pointer kernels expect, so a properly aligned `&[u8]`
+//! can be viewed as `&[U256]` without copying.
+
+use core::fmt;
+use core::mem::{align_of, size_of, size_of_val};
+use core::slice;
+
+/// Marker for plain-old-data lane types that can be viewed from raw bytes.
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]`, contain only `u64` limbs, have no
+/// padding, and accept every bit pattern as a valid value.
+pub unsafe trait Lane: Copy + Default + 'static {
+    const LIMBS: usize;
+    const BYTES: usize = Self::LIMBS * 8;
+
+    fn limbs(&self) -> &[u64] {
+        unsafe { slice::from_raw_parts(self as *const Self as *const u64, Self::LIMBS) }
+    }
+
+    fn limbs_mut(&mut self) -> &mut [u64] {
+        unsafe { slice::from_raw_parts_mut(self as *mut Self as *mut u64, Self::LIMBS) }
+    }
+}
+
+// Bare limbs, so buffers of any element width can be viewed as `&[u64]`.
+unsafe impl Lane for u64 {
+    const LIMBS: usize = 1;
+}
+
+macro_rules! lane {
+    ($name:ident, $limbs:expr) => {
+        #[repr(C, align(8))]
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub [u64; $limbs]);
+
+        unsafe impl Lane for $name {
+            const LIMBS: usize = $limbs;
+        }
+
+        impl $name {
+            pub const ZERO: Self = Self([0; $limbs]);
+
+            pub fn from_le_bytes(bytes: &[u8; $limbs * 8]) -> Self {
+                let mut limbs = [0u64; $limbs];
+                for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+                    *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+                Self(limbs)
+            }
+
+            pub fn to_le_bytes(&self) -> [u8; $limbs * 8] {
+                let mut bytes = [0u8; $limbs * 8];
+                for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0.iter()) {
+                    chunk.copy_from_slice(&limb.to_le_bytes());
+                }
+                bytes
+            }
+        }
+
+        impl From<[u64; $limbs]> for $name {
+            fn from(limbs: [u64; $limbs]) -> Self {
+                Self(limbs)
+            }
+        }
+    };
+}
+
+lane!(U256, 4);
+lane!(U512, 8);
+lane!(U1024, 16);
+lane!(U2048, 32);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CastError {
+    /// The buffer does not start on an 8-byte boundary.
+    Misaligned,
+    /// The buffer length is not a multiple of the lane size.
+    SizeMismatch,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::Misaligned => write!(f, "buffer is not 8-byte aligned"),
+            CastError::SizeMismatch => {
+                write!(f, "buffer length is not a multiple of the lane size")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CastError {}
+
+/// Empty buffers are fine whatever their pointer, an empty `Vec` or `&[]`
+/// has a dangling one. Callers must not build a slice from that pointer.
+fn check_cast<T: Lane>(bytes: &[u8]) -> Result<usize, CastError> {
+    if bytes.is_empty() {
+        return Ok(0);
+    }
+    if !(bytes.as_ptr() as usize).is_multiple_of(align_of::<T>()) {
+        return Err(CastError::Misaligned);
+    }
+    if !bytes.len().is_multiple_of(size_of::<T>()) {
+        return Err(CastError::SizeMismatch);
+    }
+    Ok(bytes.len() / size_of::<T>())
+}
+
+/// Views an aligned byte buffer as a slice of lanes.
+pub fn cast_slice<T: Lane>(bytes: &[u8]) -> Result<&[T], CastError> {
+    match check_cast::<T>(bytes)? {
+        0 => Ok(&[]),
+        len => Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, len) }),
+    }
+}
+
+/// Mutable counterpart of [`cast_slice`].
+pub fn cast_slice_mut<T: Lane>(bytes: &mut [u8]) -> Result<&mut [T], CastError> {
+    match check_cast::<T>(bytes)? {
+        0 => Ok(&mut []),
+        len => Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) }),
+    }
+}
+
+pub fn as_bytes<T: Lane>(lanes: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(lanes.as_ptr() as *const u8, size_of_val(lanes)) }
+}
+
+pub fn as_bytes_mut<T: Lane>(lanes: &mut [T]) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(lanes.as_mut_ptr() as *mut u8, size_of_val(lanes)) }
+}
+
+/// `len` lanes at `p`, for the raw pointer entry points over slice kernels.
+/// Panics unless `p` is aligned. An empty view does not touch `p`.
+pub(crate) unsafe fn from_raw<'a, T: Lane>(p: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        return &[];
+    }
+    assert!(p.is_aligned(), "lane buffer is not 8-byte aligned");
+    slice::from_raw_parts(p, len)
+}
+
+/// Mutable counterpart of [`from_raw`].
+pub(crate) unsafe fn from_raw_mut<'a, T: Lane>(p: *mut T, len: usize) -> &'a mut [T] {
+    if len == 0 {
+        return &mut [];
+    }
+    assert!(p.is_aligned(), "lane buffer is not 8-byte aligned");
+    slice::from_raw_parts_mut(p, len)
+}
+
+fn ptr<T: Lane>(lanes: &[T]) -> *const u8 {
+    lanes.as_ptr() as *const u8
+}
+
+fn ptr_mut<T: Lane>(lanes: &mut [T]) -> *mut u8 {
+    lanes.as_mut_ptr() as *mut u8
+}
+
+pub fn msbc_256(a: &U256, b: &U256) -> bool {
+    crate::msbc_256(a.0.as_ptr() as *const u8, b.0.as_ptr() as *const u8)
+}
+
+pub fn wrapping_add_256(a: &[U256], b: &[U256], dst: &mut [U256]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    crate::wrapping_add_256(ptr(a), ptr(b), ptr_mut(dst), dst.len());
+}
+
+pub fn wrapping_sub_256(a: &[U256], b: &[U256], dst: &mut [U256]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    crate::wrapping_sub_256(ptr(a), ptr(b), ptr_mut(dst), dst.len());
+}
+
+pub fn wrapping_add_512(a: &[U512], b: &[U512], dst: &mut [U512]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    crate::wrapping_add_512(ptr(a), ptr(b), ptr_mut(dst), dst.len());
+}
+
+pub fn wrapping_mul_256(a: &[U256], b: &[U256], dst: &mut [U256]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    crate::wrapping_mul_256(ptr(a), ptr(b), ptr_mut(dst), dst.len());
+}
+
+pub fn widening_mul_256(a: &[U256], b: &[U256], dst: &mut [U512]) {
+    assert!(a.len() == dst.len() && b.len() == dst.len());
+    unsafe { crate::widening_mul_256_impl(ptr(a), ptr(b), ptr_mut(dst), dst.len()) };
+}
+
+pub fn wrapping_sqr_256(a: &[U256], dst: &mut [U256]) {
+    assert!(a.len() == dst.len());
+    crate::wrapping_sqr_256(ptr(a), ptr_mut(dst), dst.len());
+}
+
+pub fn widening_sqr_256(a: &[U256], dst: &mut [U512]) {
+    assert!(a.len() == dst.len());
+    crate::widening_sqr_256(ptr(a), ptr_mut(dst), dst.len());
+}
+
+pub fn narrowing_right_shift_512(src: &[U512], dst: &mut [U256], shift: u32) {
+    assert!(src.len() == dst.len());
+    crate::narrowing_right_shift_512(ptr(src), ptr_mut(dst), shift, dst.len());
+}
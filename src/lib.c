@@ -1,6 +1,5 @@
 #include <stdint.h>
 #include <string.h>
-#include <stdio.h>
 
 void narrowing_right_shift_512_c_impl(const uint64_t *src, uint64_t *dst, uint32_t shift, uint64_t len) {
   shift = shift & 511;
@@ -0,0 +1,191 @@
+//! Portable limb arithmetic shared by the modular and generic kernels.
+//!
+//! Numbers are little-endian slices of `u64` limbs. Carries and borrows are
+//! passed around as `u64` values of 0 or 1.
+
+use core::cmp::Ordering;
+
+#[inline(always)]
+pub fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+pub fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// Computes `acc + a * b + carry`, returning the low limb and the carry.
+#[inline(always)]
+pub fn mac(acc: u64, a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = acc as u128 + a as u128 * b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// `a += b`, returning the carry out. `b` may be shorter than `a`.
+#[inline(always)]
+pub fn add_assign(a: &mut [u64], b: &[u64]) -> u64 {
+    let mut carry = 0;
+    for (i, x) in a.iter_mut().enumerate() {
+        (*x, carry) = adc(*x, b.get(i).copied().unwrap_or(0), carry);
+    }
+    carry
+}
+
+/// `a -= b`, returning the borrow out. `b` may be shorter than `a`.
+#[inline(always)]
+pub fn sub_assign(a: &mut [u64], b: &[u64]) -> u64 {
+    let mut borrow = 0;
+    for (i, x) in a.iter_mut().enumerate() {
+        (*x, borrow) = sbb(*x, b.get(i).copied().unwrap_or(0), borrow);
+    }
+    borrow
+}
+
+pub fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    debug_assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        match x.cmp(y) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    Ordering::Equal
+}
+
+/// Product scanning: each output limb is one column of partial products
+/// summed into a 192-bit accumulator. Only as many columns as `dst` holds
+/// are computed, so a `dst` as long as the inputs gives the wrapping product.
+#[inline(always)]
+pub fn comba(a: &[u64], b: &[u64], dst: &mut [u64]) {
+    debug_assert_eq!(a.len(), b.len());
+    let n = a.len();
+    let mut acc = 0u128;
+    for (k, d) in dst.iter_mut().enumerate() {
+        let mut overflow = 0u64;
+        for i in k.saturating_sub(n - 1)..=k.min(n - 1) {
+            let (s, o) = acc.overflowing_add(a[i] as u128 * b[k - i] as u128);
+            acc = s;
+            overflow += o as u64;
+        }
+        *d = acc as u64;
+        acc = (acc >> 64) | ((overflow as u128) << 64);
+    }
+}
+
+/// Number of limbs up to and including the most significant non-zero one.
+pub fn significant(a: &[u64]) -> usize {
+    a.iter().rposition(|l| *l != 0).map_or(0, |i| i + 1)
+}
+
+/// Computes `u mod m` with Knuth's algorithm D, writing the remainder into
+/// `rem`, which must be as long as `m`. `m` must be non-zero.
+pub fn rem(u: &[u64], m: &[u64], rem: &mut [u64]) {
+    divide(u, m, None, rem)
+}
+
+/// Like [`rem`], but also writes the quotient into `quot`, which must be as
+/// long as `u`.
+pub fn div_rem(u: &[u64], m: &[u64], quot: &mut [u64], rem: &mut [u64]) {
+    debug_assert_eq!(u.len(), quot.len());
+    quot.fill(0);
+    divide(u, m, Some(quot), rem)
+}
+
+fn divide(u: &[u64], m: &[u64], mut quot: Option<&mut [u64]>, rem: &mut [u64]) {
+    debug_assert_eq!(m.len(), rem.len());
+    let n = significant(m);
+    assert!(n > 0, "division by zero");
+    rem.fill(0);
+
+    if n == 1 {
+        let d = m[0] as u128;
+        let mut r = 0u128;
+        for (i, limb) in u.iter().enumerate().rev() {
+            let num = (r << 64) | *limb as u128;
+            if let Some(q) = quot.as_deref_mut() {
+                q[i] = (num / d) as u64;
+            }
+            r = num % d;
+        }
+        rem[0] = r as u64;
+        return;
+    }
+    if significant(u) < n {
+        let len = u.len().min(rem.len());
+        rem[..len].copy_from_slice(&u[..len]);
+        return;
+    }
+
+    // Normalize so the top limb of the divisor has its high bit set, which
+    // keeps every quotient digit estimate within 2 of the real one.
+    let s = m[n - 1].leading_zeros();
+    let mut v = [0u64; 32];
+    let mut un = [0u64; 65];
+    assert!(n <= v.len() && u.len() < un.len());
+    shl(&m[..n], s, &mut v[..n]);
+    un[u.len()] = shl(u, s, &mut un[..u.len()]);
+
+    let b = 1u128 << 64;
+    for j in (0..=u.len() - n).rev() {
+        let num = ((un[j + n] as u128) << 64) | un[j + n - 1] as u128;
+        let mut qhat = num / v[n - 1] as u128;
+        let mut rhat = num % v[n - 1] as u128;
+        while qhat >= b || qhat * v[n - 2] as u128 > ((rhat << 64) | un[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += v[n - 1] as u128;
+            if rhat >= b {
+                break;
+            }
+        }
+
+        let mut carry = 0;
+        let mut borrow = 0;
+        for i in 0..n {
+            let (p, c) = mac(0, qhat as u64, v[i], carry);
+            carry = c;
+            (un[i + j], borrow) = sbb(un[i + j], p, borrow);
+        }
+        (un[j + n], borrow) = sbb(un[j + n], carry, borrow);
+
+        // The estimate was one too large, add the divisor back
+        if borrow != 0 {
+            qhat -= 1;
+            let carry = add_assign(&mut un[j..j + n], &v[..n]);
+            un[j + n] = un[j + n].wrapping_add(carry);
+        }
+        if let Some(q) = quot.as_deref_mut() {
+            q[j] = qhat as u64;
+        }
+    }
+
+    shr(&un[..n], s, &mut rem[..n]);
+}
+
+/// Writes `a << s` into `dst` for `s < 64`, returning the bits shifted out.
+pub fn shl(a: &[u64], s: u32, dst: &mut [u64]) -> u64 {
+    if s == 0 {
+        dst.copy_from_slice(a);
+        return 0;
+    }
+    let mut out = 0;
+    for (d, x) in dst.iter_mut().zip(a.iter()) {
+        *d = (x << s) | out;
+        out = x >> (64 - s);
+    }
+    out
+}
+
+/// Writes `a >> s` into `dst` for `s < 64`.
+pub fn shr(a: &[u64], s: u32, dst: &mut [u64]) {
+    if s == 0 {
+        dst.copy_from_slice(a);
+        return;
+    }
+    for i in 0..a.len() {
+        let hi = a.get(i + 1).map_or(0, |x| x << (64 - s));
+        dst[i] = (a[i] >> s) | hi;
+    }
+}
@@ -0,0 +1,418 @@
+//! Batch modular arithmetic on 256-bit lanes sharing one modulus.
+
+use crate::ct::{self, cswap, select};
+use crate::lane::{self, U256, U512};
+use crate::limb;
+use core::cmp::Ordering;
+
+const CHUNK: usize = 16;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Modulus256 {
+    m: U256,
+}
+
+impl Modulus256 {
+    /// Returns `None` for a zero modulus.
+    pub fn new(m: U256) -> Option<Self> {
+        if m == U256::ZERO {
+            None
+        } else {
+            Some(Self { m })
+        }
+    }
+
+    pub fn modulus(&self) -> &U256 {
+        &self.m
+    }
+
+    /// Reduces arbitrary 256-bit lanes into `[0, m)`.
+    pub fn reduce(&self, a: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            limb::rem(&x.0, &self.m.0, &mut d.0);
+        }
+    }
+
+    /// Computes `(a + b) mod m`. Inputs must already be reduced.
+    pub fn addmod(&self, a: &[U256], b: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len() && b.len() == dst.len());
+        for i in 0..dst.len() {
+            let mut s = a[i];
+            let carry = limb::add_assign(&mut s.0, &b[i].0);
+            if carry != 0 || limb::cmp(&s.0, &self.m.0) != Ordering::Less {
+                limb::sub_assign(&mut s.0, &self.m.0);
+            }
+            dst[i] = s;
+        }
+    }
+
+    /// Computes `(a - b) mod m`. Inputs must already be reduced.
+    pub fn submod(&self, a: &[U256], b: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len() && b.len() == dst.len());
+        for i in 0..dst.len() {
+            let mut d = a[i];
+            if limb::sub_assign(&mut d.0, &b[i].0) != 0 {
+                limb::add_assign(&mut d.0, &self.m.0);
+            }
+            dst[i] = d;
+        }
+    }
+
+    /// Computes `(a * b) mod m`. Inputs may be any 256-bit values.
+    pub fn mulmod(&self, a: &[U256], b: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len() && b.len() == dst.len());
+        let mut wide = [U512::ZERO; CHUNK];
+        for start in (0..dst.len()).step_by(CHUNK) {
+            let n = core::cmp::min(CHUNK, dst.len() - start);
+            lane::widening_mul_256(&a[start..start + n], &b[start..start + n], &mut wide[..n]);
+            for (w, d) in wide[..n].iter().zip(dst[start..start + n].iter_mut()) {
+                limb::rem(&w.0, &self.m.0, &mut d.0);
+            }
+        }
+    }
+}
+
+/// Montgomery form context for an odd 256-bit modulus, with `R = 2^256`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Montgomery256 {
+    m: U256,
+    /// `-m^-1 mod 2^64`
+    inv: u64,
+    /// `R^2 mod m`
+    r2: U256,
+}
+
+impl Montgomery256 {
+    /// Returns `None` for an even modulus, which has no Montgomery form.
+    pub fn new(m: U256) -> Option<Self> {
+        if m.0[0] & 1 == 0 {
+            return None;
+        }
+        // Newton iteration doubles the number of correct low bits each step,
+        // m0 itself is already a correct inverse mod 2^3.
+        let mut inv = m.0[0];
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m.0[0].wrapping_mul(inv)));
+        }
+        let mut r_squared = [0u64; 9];
+        r_squared[8] = 1;
+        let mut r2 = U256::ZERO;
+        limb::rem(&r_squared, &m.0, &mut r2.0);
+        Some(Self {
+            m,
+            inv: inv.wrapping_neg(),
+            r2,
+        })
+    }
+
+    pub fn modulus(&self) -> &U256 {
+        &self.m
+    }
+
+    /// Converts lanes into Montgomery form. Inputs may be any 256-bit values.
+    pub fn to_mont(&self, a: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            *d = self.mul_one(x, &self.r2);
+        }
+    }
+
+    /// Converts lanes out of Montgomery form.
+    pub fn from_mont(&self, a: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        let one = U256([1, 0, 0, 0]);
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            *d = self.mul_one(x, &one);
+        }
+    }
+
+    /// Computes `a * b * R^-1 mod m`. Inputs must be in Montgomery form.
+    pub fn mont_mul(&self, a: &[U256], b: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len() && b.len() == dst.len());
+        for i in 0..dst.len() {
+            dst[i] = self.mul_one(&a[i], &b[i]);
+        }
+    }
+
+    /// Computes `a * a * R^-1 mod m`. Inputs must be in Montgomery form.
+    pub fn mont_sqr(&self, a: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            *d = self.mul_one(x, x);
+        }
+    }
+
+    /// Computes `a^e mod m` for every lane with one shared exponent. Inputs
+    /// and outputs are in normal form.
+    pub fn powmod(&self, a: &[U256], e: &U256, dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            *d = self.pow_one(x, e);
+        }
+    }
+
+    /// Computes `a[i]^e[i] mod m` with a separate exponent per lane.
+    pub fn powmod_each(&self, a: &[U256], e: &[U256], dst: &mut [U256]) {
+        assert!(a.len() == dst.len() && e.len() == dst.len());
+        for i in 0..dst.len() {
+            dst[i] = self.pow_one(&a[i], &e[i]);
+        }
+    }
+
+    /// Inverts lanes with Fermat's little theorem as `a^(m-2)`, which is only
+    /// meaningful for a prime modulus. Zero lanes stay zero.
+    pub fn invert_prime(&self, a: &[U256], dst: &mut [U256]) {
+        let mut e = self.m;
+        limb::sub_assign(&mut e.0, &[2]);
+        self.powmod(a, &e, dst);
+    }
+
+    /// Inverts lanes with a constant-time binary extended GCD, which works
+    /// for any odd modulus. Lanes sharing a factor with `m` have no inverse
+    /// and are written as zero, in which case `false` is returned.
+    pub fn invert(&self, a: &[U256], dst: &mut [U256]) -> bool {
+        assert!(a.len() == dst.len());
+        let mut all = true;
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            let (inv, ok) = self.invert_one(x);
+            *d = inv;
+            all &= ok;
+        }
+        all
+    }
+
+    /// Fixed 4-bit window over all 64 exponent digits, with every table
+    /// lookup scanning the whole table, so the sequence of operations does
+    /// not depend on the exponent.
+    fn pow_one(&self, a: &U256, e: &U256) -> U256 {
+        let mut table = [U256::ZERO; 16];
+        table[0] = self.mul_one(&U256([1, 0, 0, 0]), &self.r2);
+        table[1] = self.mul_one(a, &self.r2);
+        for i in 2..16 {
+            table[i] = self.mul_one(&table[i - 1], &table[1]);
+        }
+
+        let mut acc = table[0];
+        for digit in (0..64).rev() {
+            for _ in 0..4 {
+                acc = self.mul_one(&acc, &acc);
+            }
+            let index = (e.0[digit / 16] >> ((digit % 16) * 4)) & 0xf;
+            let mut entry = U256::ZERO;
+            for (i, t) in table.iter().enumerate() {
+                select(!ct::mask_nonzero(i as u64 ^ index), &mut entry.0, &t.0);
+            }
+            acc = self.mul_one(&acc, &entry);
+        }
+        self.mul_one(&acc, &U256([1, 0, 0, 0]))
+    }
+
+    /// Binary GCD in the style of Nettle's `sec_invert`. The invariants are
+    /// `a = u * x` and `b = v * x` mod m, every step halves `a` after making
+    /// it even, and 512 steps are enough to drive `a` to zero for 256-bit
+    /// inputs, leaving the GCD in `b` and the inverse in `v`.
+    fn invert_one(&self, x: &U256) -> (U256, bool) {
+        let m = &self.m.0;
+        let mut a = x.0;
+        let mut b = *m;
+        let mut u = [1u64, 0, 0, 0];
+        let mut v = [0u64; 4];
+        for _ in 0..512 {
+            let odd = ct::mask_bit(a[0] & 1);
+
+            // a -= b when a is odd, swapping in b - a if that underflows
+            let mut t = [0u64; 4];
+            select(odd, &mut t, &b);
+            let swap = ct::mask_bit(limb::sub_assign(&mut a, &t));
+            let mut d = [0u64; 4];
+            select(swap, &mut d, &a);
+            limb::add_assign(&mut b, &d);
+            let mut neg = [0u64; 4];
+            limb::sub_assign(&mut neg, &a);
+            select(swap, &mut a, &neg);
+
+            // Mirror the same on the cofactors, mod m
+            cswap(swap, &mut u, &mut v);
+            let mut t = [0u64; 4];
+            select(odd, &mut t, &v);
+            let borrow = ct::mask_bit(limb::sub_assign(&mut u, &t));
+            let mut fix = [0u64; 4];
+            select(borrow, &mut fix, m);
+            limb::add_assign(&mut u, &fix);
+
+            // a and u halve together, adding m first when u is odd
+            let t = a;
+            limb::shr(&t, 1, &mut a);
+            let mut fix = [0u64; 4];
+            select(ct::mask_bit(u[0] & 1), &mut fix, m);
+            let carry = limb::add_assign(&mut u, &fix);
+            let t = u;
+            limb::shr(&t, 1, &mut u);
+            u[3] |= carry << 63;
+        }
+
+        let ok = !ct::mask_nonzero((b[0] ^ 1) | b[1] | b[2] | b[3]);
+        let mut inv = U256::ZERO;
+        select(ok, &mut inv.0, &v);
+        (inv, ok != 0)
+    }
+
+    /// Coarsely integrated operand scanning: each row of the product is
+    /// followed straight away by one limb of reduction, so the accumulator
+    /// never grows past 6 limbs. The result is correct as long as one of the
+    /// operands is below `m`.
+    #[inline(always)]
+    pub(crate) fn mul_one(&self, a: &U256, b: &U256) -> U256 {
+        let m = &self.m.0;
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0;
+            for (t, a) in t.iter_mut().zip(a.0.iter()) {
+                (*t, carry) = limb::mac(*t, *a, b.0[i], carry);
+            }
+            (t[4], carry) = limb::adc(t[4], carry, 0);
+            t[5] = carry;
+
+            let q = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = limb::mac(t[0], q, m[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = limb::mac(t[j], q, m[j], carry);
+            }
+            (t[3], carry) = limb::adc(t[4], carry, 0);
+            t[4] = t[5] + carry;
+        }
+
+        // Subtract m once more unless that underflows, without branching
+        let mut r = [t[0], t[1], t[2], t[3], t[4]];
+        let borrow = limb::sub_assign(&mut r, m);
+        select(ct::mask_bit(borrow), &mut r, &t[..4]);
+        U256([r[0], r[1], r[2], r[3]])
+    }
+}
+
+/// Barrett reduction context for an arbitrary non-zero 256-bit modulus.
+///
+/// The modulus is normalized to `m' = m << shift` so its top bit is set,
+/// which keeps the quotient estimate within 2 of the real one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Barrett256 {
+    m: U256,
+    shift: u32,
+    /// `m << shift`
+    norm: [u64; 4],
+    /// `floor(2^512 / norm)`, always just above `2^256`
+    mu: [u64; 5],
+}
+
+impl Barrett256 {
+    /// Returns `None` for a zero modulus.
+    pub fn new(m: U256) -> Option<Self> {
+        let n = limb::significant(&m.0);
+        if n == 0 {
+            return None;
+        }
+        let shift = m.0[n - 1].leading_zeros() + 64 * (4 - n as u32);
+        let mut norm = [0u64; 4];
+        shl_256(&m.0, shift, &mut norm);
+
+        let mut b8 = [0u64; 9];
+        b8[8] = 1;
+        let mut quot = [0u64; 9];
+        limb::div_rem(&b8, &norm, &mut quot, &mut [0u64; 4]);
+        let mut mu = [0u64; 5];
+        mu.copy_from_slice(&quot[..5]);
+        Some(Self { m, shift, norm, mu })
+    }
+
+    pub fn modulus(&self) -> &U256 {
+        &self.m
+    }
+
+    /// Reduces 512-bit lanes, such as the output of
+    /// [`lane::widening_mul_256`], into `[0, m)`.
+    pub fn reduce_512(&self, a: &[U512], dst: &mut [U256]) {
+        assert!(a.len() == dst.len());
+        for (x, d) in a.iter().zip(dst.iter_mut()) {
+            *d = self.reduce_one(x);
+        }
+    }
+
+    #[inline(always)]
+    fn reduce_one(&self, x: &U512) -> U256 {
+        let s = self.shift;
+        let (lo, hi) = x.0.split_at(4);
+        let mut y = [0u64; 8];
+        if s == 0 {
+            y.copy_from_slice(&x.0);
+        } else {
+            // Shifting the whole input could overflow 512 bits, so fold the
+            // high half first: (hi << s) mod m' is (hi mod m) << s, and
+            // appending the shifted low half then stays below m' * 2^256. The
+            // two never overlap since the folded half has s low zero bits.
+            let mut t = [0u64; 8];
+            shl_256(hi.try_into().unwrap(), s, &mut t);
+            let top = self.barrett(&t);
+            shl_256(lo.try_into().unwrap(), s, &mut y);
+            for (y, top) in y[4..].iter_mut().zip(top.iter()) {
+                *y |= top;
+            }
+        }
+        let r = self.barrett(&y);
+        let mut d = U256::ZERO;
+        shr_256(&r, s, &mut d.0);
+        d
+    }
+
+    /// HAC algorithm 14.42 with `b = 2^64` and `k = 4`, for `x < 2^512`.
+    #[inline(always)]
+    fn barrett(&self, x: &[u64; 8]) -> [u64; 4] {
+        let mut q2 = [0u64; 10];
+        for (i, mu) in self.mu.iter().enumerate() {
+            let mut carry = 0;
+            for j in 0..5 {
+                (q2[i + j], carry) = limb::mac(q2[i + j], x[j + 3], *mu, carry);
+            }
+            q2[i + 5] = carry;
+        }
+        let q3 = &q2[5..];
+
+        let mut r2 = [0u64; 5];
+        for (i, q) in q3.iter().enumerate() {
+            let mut carry = 0;
+            for j in 0..(5 - i).min(4) {
+                (r2[i + j], carry) = limb::mac(r2[i + j], *q, self.norm[j], carry);
+            }
+            if i + 4 < 5 {
+                r2[i + 4] = r2[i + 4].wrapping_add(carry);
+            }
+        }
+
+        let mut r = [0u64; 5];
+        r.copy_from_slice(&x[..5]);
+        limb::sub_assign(&mut r, &r2);
+        while r[4] != 0 || limb::cmp(&r[..4], &self.norm) != Ordering::Less {
+            limb::sub_assign(&mut r, &self.norm);
+        }
+        [r[0], r[1], r[2], r[3]]
+    }
+}
+
+/// Writes `a << s` for `s < 256` into `dst`, which may be up to 8 limbs.
+fn shl_256(a: &[u64; 4], s: u32, dst: &mut [u64]) {
+    let (limbs, bits) = ((s / 64) as usize, s % 64);
+    let mut t = [0u64; 9];
+    t[4] = limb::shl(a, bits, &mut t[..4]);
+    let mut shifted = [0u64; 9];
+    shifted[limbs..].copy_from_slice(&t[..9 - limbs]);
+    let n = dst.len();
+    dst.copy_from_slice(&shifted[..n]);
+}
+
+/// Writes `a >> s` for `s < 256` into `dst`.
+fn shr_256(a: &[u64; 4], s: u32, dst: &mut [u64; 4]) {
+    let (limbs, bits) = ((s / 64) as usize, s % 64);
+    let mut t = [0u64; 4];
+    t[..4 - limbs].copy_from_slice(&a[limbs..]);
+    limb::shr(&t, bits, dst);
+}
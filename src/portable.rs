@@ -0,0 +1,116 @@
+//! The root kernels in plain Rust, for targets without an asm backend.
+//!
+//! Same names, signatures and memory layout as the crate root, which
+//! re-exports this module where no asm backend exists. Elements are read
+//! and written unaligned through [`LittleEndian`], and each one is loaded
+//! in full before its result is stored, so `dst` may alias a source.
+
+use crate::endian::{ByteOrder, LittleEndian};
+use crate::generic::Limbs;
+use crate::limb;
+
+#[inline(always)]
+fn add<const N: usize>(a: &Limbs<N>, b: &Limbs<N>, dst: &mut Limbs<N>) {
+    *dst = *a;
+    limb::add_assign(&mut dst.0, &b.0);
+}
+
+#[inline(always)]
+fn sub<const N: usize>(a: &Limbs<N>, b: &Limbs<N>, dst: &mut Limbs<N>) {
+    *dst = *a;
+    limb::sub_assign(&mut dst.0, &b.0);
+}
+
+/// Wrapping or widening, `limb::comba` stops at the width of `dst`.
+#[inline(always)]
+fn mul<const N: usize, const M: usize>(a: &Limbs<N>, b: &Limbs<N>, dst: &mut Limbs<M>) {
+    limb::comba(&a.0, &b.0, &mut dst.0);
+}
+
+macro_rules! binary {
+    ($name:ident, $strided:ident, $n:expr, $m:expr, $op:expr) => {
+        #[inline(never)]
+        pub fn $name(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+            $strided(a, 8 * $n, b, 8 * $n, dst, 8 * $m, len);
+        }
+
+        binary!($strided, $n, $m, $op);
+    };
+    ($strided:ident, $n:expr, $m:expr, $op:expr) => {
+        #[inline(never)]
+        pub fn $strided(
+            a: *const u8,
+            a_stride: isize,
+            b: *const u8,
+            b_stride: isize,
+            dst: *mut u8,
+            dst_stride: isize,
+            len: usize,
+        ) {
+            for i in 0..len as isize {
+                unsafe {
+                    let x: Limbs<$n> = LittleEndian::load(a.wrapping_offset(i * a_stride));
+                    let y: Limbs<$n> = LittleEndian::load(b.wrapping_offset(i * b_stride));
+                    let mut z = Limbs::<$m>::ZERO;
+                    $op(&x, &y, &mut z);
+                    LittleEndian::store(dst.wrapping_offset(i * dst_stride), &z);
+                }
+            }
+        }
+    };
+}
+
+binary!(wrapping_add_256, wrapping_add_256_strided, 4, 4, add);
+binary!(wrapping_add_512, wrapping_add_512_strided, 8, 8, add);
+binary!(wrapping_sub_256, wrapping_sub_256_strided, 4, 4, sub);
+binary!(wrapping_sub_512, wrapping_sub_512_strided, 8, 8, sub);
+binary!(wrapping_mul_256, wrapping_mul_256_strided, 4, 4, mul);
+binary!(wrapping_mul_512, wrapping_mul_512_strided, 8, 8, mul);
+binary!(widening_mul_256_strided, 4, 8, mul);
+binary!(widening_mul_512, widening_mul_512_strided, 8, 16, mul);
+
+/// The pointer form behind the crate root's byte-offset `widening_mul_256`,
+/// unused where an asm backend provides its own.
+#[allow(dead_code)]
+#[inline(always)]
+pub(crate) unsafe fn widening_mul_256_impl(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, b, 32, dst, 64, len);
+}
+
+#[inline(never)]
+pub fn widening_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, a, 32, dst, 64, len);
+}
+
+#[inline(never)]
+pub fn widening_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    widening_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    wrapping_mul_256(a, a, dst, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    wrapping_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
+
+pub fn msbc_256(a: *const u8, b: *const u8) -> bool {
+    let (mut x, y): (Limbs<4>, Limbs<4>) =
+        unsafe { (LittleEndian::load(a), LittleEndian::load(b)) };
+    limb::sub_assign(&mut x.0, &y.0) != 0
+}
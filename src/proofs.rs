@@ -0,0 +1,373 @@
+//! Kani proof harnesses for the portable kernels and a model of the x86_64
+//! asm, built only by `cargo kani`:
+//!
+//! ```sh
+//! cargo install --locked kani-verifier && cargo kani setup
+//! cargo kani --harness wrapping_add_256
+//! cargo kani
+//! ```
+//!
+//! Each harness runs one element of a [`crate::portable`] kernel through its
+//! raw pointer entry point on symbolic bytes and compares it with a separate
+//! specification below, written limb by limb with `overflowing_add`,
+//! `overflowing_sub` and schoolbook rows instead of the `u128` carry
+//! helpers and product scanning of [`crate::limb`]. Kani also checks every
+//! arithmetic overflow and out-of-bounds access on the way, which covers the
+//! 192-bit column accumulator in `limb::comba`.
+//!
+//! Kani cannot model inline asm itself. Instead build.rs emits the exact
+//! instruction sequence of every generated x86_64 kernel a second time, as
+//! calls on the `x86_64` machine model below, and the harnesses in `asm`
+//! hold that to the same specification. What this does not cover is the
+//! asm! wrapper around the instructions (operand registers, clobbers) and
+//! the hand-written `msbc_256`; those, and the AArch64 and RVV asm, are held
+//! to the portable kernels by the differential tests and the fuzz targets.
+//!
+//! Unwinding assertions are on, so a bound that is too small fails the
+//! harness instead of passing it silently. Every loop in a harness runs over
+//! the limbs of one operand or result, in `limbs`, the specifications,
+//! `LittleEndian` loads and stores, `limb::add_assign` and the columns of
+//! `limb::comba`, and a loop of `n` trips needs a bound of `n + 1`. So each
+//! bound is the widest operand or result in limbs plus one, which the
+//! harness macros check at compile time. The multiplication harnesses are
+//! by far the slowest, the 512-bit ones may need a long timeout.
+
+use crate::portable;
+
+fn limbs<const N: usize>(bytes: &[u8]) -> [u64; N] {
+    let mut limbs = [0u64; N];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[8 * i..8 * i + 8]);
+        *limb = u64::from_le_bytes(word);
+    }
+    limbs
+}
+
+fn spec_add<const N: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+    let mut r = [0u64; N];
+    let mut carry = false;
+    for i in 0..N {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        r[i] = s;
+        carry = c1 || c2;
+    }
+    r
+}
+
+/// The difference and whether it borrowed.
+fn spec_sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut r = [0u64; N];
+    let mut borrow = false;
+    for i in 0..N {
+        let (s, b1) = a[i].overflowing_sub(b[i]);
+        let (s, b2) = s.overflowing_sub(borrow as u64);
+        r[i] = s;
+        borrow = b1 || b2;
+    }
+    (r, borrow)
+}
+
+fn spec_wrapping_sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+    spec_sub(a, b).0
+}
+
+/// Low `M` limbs of `a * b`, row by row.
+fn spec_mul<const N: usize, const M: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; M] {
+    let mut r = [0u64; M];
+    for i in 0..N {
+        let mut carry = 0u64;
+        for j in 0..N {
+            if i + j >= M {
+                break;
+            }
+            let t = a[i] as u128 * b[j] as u128 + r[i + j] as u128 + carry as u128;
+            r[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        if i + N < M {
+            r[i + N] = carry;
+        }
+    }
+    r
+}
+
+/// Low 256 bits of `src >> (shift & 511)`, from 128-bit windows.
+fn spec_shr(src: &[u64; 8], shift: u32) -> [u64; 4] {
+    let shift = shift & 511;
+    let limb = |i: usize| src.get(i).copied().unwrap_or(0) as u128;
+    let (q, r) = ((shift / 64) as usize, shift % 64);
+    let mut dst = [0u64; 4];
+    for (i, d) in dst.iter_mut().enumerate() {
+        let window = (limb(q + i + 1) << 64) | limb(q + i);
+        *d = (window >> r) as u64;
+    }
+    dst
+}
+
+/// `n` limbs in, `out` limbs out.
+const fn unwind(n: usize, out: usize) -> u32 {
+    if n > out {
+        n as u32 + 1
+    } else {
+        out as u32 + 1
+    }
+}
+
+macro_rules! binary_proof {
+    ($name:ident, $n:literal, $out:literal, $unwind:literal, $spec:expr) => {
+        #[kani::proof]
+        #[kani::unwind($unwind)]
+        fn $name() {
+            const _: () = assert!($unwind == unwind($n, $out));
+            let a: [u8; 8 * $n] = kani::any();
+            let b: [u8; 8 * $n] = kani::any();
+            let mut dst = [0u8; 8 * $out];
+            portable::$name(a.as_ptr(), b.as_ptr(), dst.as_mut_ptr(), 1);
+
+            let expected: [u64; $out] = $spec(&limbs(&a), &limbs(&b));
+            assert_eq!(limbs::<$out>(&dst), expected);
+        }
+    };
+}
+
+binary_proof!(wrapping_add_256, 4, 4, 5, spec_add::<4>);
+binary_proof!(wrapping_add_512, 8, 8, 9, spec_add::<8>);
+binary_proof!(wrapping_sub_256, 4, 4, 5, spec_wrapping_sub::<4>);
+binary_proof!(wrapping_sub_512, 8, 8, 9, spec_wrapping_sub::<8>);
+binary_proof!(wrapping_mul_256, 4, 4, 5, spec_mul::<4, 4>);
+binary_proof!(wrapping_mul_512, 8, 8, 9, spec_mul::<8, 8>);
+binary_proof!(widening_mul_512, 8, 16, 17, spec_mul::<8, 16>);
+
+#[kani::proof]
+#[kani::unwind(9)]
+fn widening_mul_256() {
+    let a: [u8; 32] = kani::any();
+    let b: [u8; 32] = kani::any();
+    let mut dst = [0u8; 64];
+    portable::widening_mul_256_strided(a.as_ptr(), 32, b.as_ptr(), 32, dst.as_mut_ptr(), 64, 1);
+
+    assert_eq!(limbs::<8>(&dst), spec_mul::<4, 8>(&limbs(&a), &limbs(&b)));
+}
+
+/// [`portable::widening_mul_256_strided`] against a 4x4 schoolbook written
+/// out row by row, with the carries checked as they happen. Row `i` adds
+/// `a[i] * b`, below `a[i] * 2^256`, onto four limbs below `2^256`, so its
+/// carry out into `r[i + 4]` is at most `a[i]`, and after it `r[i]` is final
+/// and must already match `dst`. A carry dropped anywhere in a row, the
+/// portable counterpart of a missing `adc rdx, 0`, breaks one of these.
+#[kani::proof]
+#[kani::unwind(9)]
+fn widening_mul_256_schoolbook() {
+    let a: [u8; 32] = kani::any();
+    let b: [u8; 32] = kani::any();
+    let mut dst = [0u8; 64];
+    portable::widening_mul_256_strided(a.as_ptr(), 32, b.as_ptr(), 32, dst.as_mut_ptr(), 64, 1);
+    let (a, b, dst) = (limbs::<4>(&a), limbs::<4>(&b), limbs::<8>(&dst));
+
+    let mut r = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u64;
+        for j in 0..4 {
+            let t = a[i] as u128 * b[j] as u128 + r[i + j] as u128 + carry as u128;
+            r[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        assert!(carry <= a[i], "row {} carries out more than a[{}]", i, i);
+        r[i + 4] = carry;
+        assert_eq!(dst[i], r[i], "limb {} after row {}", i, i);
+    }
+    assert_eq!(dst, r);
+}
+
+#[kani::proof]
+#[kani::unwind(9)]
+fn widening_sqr_256() {
+    let a: [u8; 32] = kani::any();
+    let mut dst = [0u8; 64];
+    portable::widening_sqr_256(a.as_ptr(), dst.as_mut_ptr(), 1);
+
+    let a = limbs(&a);
+    assert_eq!(limbs::<8>(&dst), spec_mul::<4, 8>(&a, &a));
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn msbc_256() {
+    let a: [u8; 32] = kani::any();
+    let b: [u8; 32] = kani::any();
+
+    let borrow = portable::msbc_256(a.as_ptr(), b.as_ptr());
+
+    assert_eq!(borrow, spec_sub::<4>(&limbs(&a), &limbs(&b)).1);
+}
+
+/// The shift is plain Rust on every target, so this one covers the crate
+/// root kernel itself.
+#[kani::proof]
+#[kani::unwind(9)]
+fn narrowing_right_shift_512() {
+    let src: [u8; 64] = kani::any();
+    let shift: u32 = kani::any();
+    let mut dst = [0u8; 32];
+    crate::narrowing_right_shift_512(src.as_ptr(), dst.as_mut_ptr(), shift, 1);
+
+    assert_eq!(limbs::<4>(&dst), spec_shr(&limbs(&src), shift));
+}
+
+/// Adding then subtracting the same value is the identity, across every
+/// carry and borrow chain.
+#[kani::proof]
+#[kani::unwind(5)]
+fn add_sub_roundtrip_256() {
+    let a: [u8; 32] = kani::any();
+    let b: [u8; 32] = kani::any();
+    let mut sum = [0u8; 32];
+    let mut dst = [0u8; 32];
+    portable::wrapping_add_256(a.as_ptr(), b.as_ptr(), sum.as_mut_ptr(), 1);
+    portable::wrapping_sub_256(sum.as_ptr(), b.as_ptr(), dst.as_mut_ptr(), 1);
+
+    assert_eq!(dst, a);
+}
+
+/// Just enough of x86_64 for the instructions build.rs generates: the
+/// general purpose registers, the carry flag, and `a`, `b` and `dst` as the
+/// memory behind `rsi`, `rcx` and `rdi`.
+mod x86_64 {
+    pub(super) use self::Base::*;
+    pub(super) use self::Operand::*;
+
+    pub(super) const RAX: Operand = Reg(0);
+    pub(super) const RDX: Operand = Reg(2);
+
+    #[derive(Copy, Clone)]
+    pub(super) enum Base {
+        A,
+        B,
+        Dst,
+    }
+
+    #[derive(Copy, Clone)]
+    pub(super) enum Operand {
+        Reg(usize),
+        /// Base and limb index
+        Mem(Base, usize),
+        Imm(u64),
+    }
+
+    pub(super) struct Machine {
+        pub(super) reg: [u64; 16],
+        pub(super) cf: bool,
+        pub(super) a: [u64; 8],
+        pub(super) b: [u64; 8],
+        pub(super) dst: [u64; 16],
+    }
+
+    impl Machine {
+        fn read(&self, operand: Operand) -> u64 {
+            match operand {
+                Reg(r) => self.reg[r],
+                Mem(A, i) => self.a[i],
+                Mem(B, i) => self.b[i],
+                Mem(Dst, i) => self.dst[i],
+                Imm(x) => x,
+            }
+        }
+
+        fn write(&mut self, operand: Operand, value: u64) {
+            match operand {
+                Reg(r) => self.reg[r] = value,
+                Mem(A, _) | Mem(B, _) => panic!("write to an operand"),
+                Mem(Dst, i) => self.dst[i] = value,
+                Imm(_) => unreachable!(),
+            }
+        }
+
+        /// Leaves the flags alone.
+        pub(super) fn mov(&mut self, dst: Operand, src: Operand) {
+            self.write(dst, self.read(src));
+        }
+
+        pub(super) fn add(&mut self, dst: Operand, src: Operand) {
+            self.cf = false;
+            self.adc(dst, src);
+        }
+
+        pub(super) fn adc(&mut self, dst: Operand, src: Operand) {
+            let (x, c1) = self.read(dst).overflowing_add(self.read(src));
+            let (x, c2) = x.overflowing_add(self.cf as u64);
+            self.write(dst, x);
+            self.cf = c1 || c2;
+        }
+
+        pub(super) fn sub(&mut self, dst: Operand, src: Operand) {
+            self.cf = false;
+            self.sbb(dst, src);
+        }
+
+        pub(super) fn sbb(&mut self, dst: Operand, src: Operand) {
+            let (x, b1) = self.read(dst).overflowing_sub(self.read(src));
+            let (x, b2) = x.overflowing_sub(self.cf as u64);
+            self.write(dst, x);
+            self.cf = b1 || b2;
+        }
+
+        /// `rdx:rax = rax * src`, setting the carry flag when `rdx` is not
+        /// zero.
+        pub(super) fn mul(&mut self, src: Operand) {
+            let product = self.reg[0] as u128 * self.read(src) as u128;
+            self.reg[0] = product as u64;
+            self.reg[2] = (product >> 64) as u64;
+            self.cf = self.reg[2] != 0;
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/model_x86_64.rs"));
+}
+
+/// One generated x86_64 kernel on the machine model against the same
+/// specification as its portable kernel. Registers, flags and `dst` start
+/// out symbolic, so the asm must not depend on any of them, and the limbs
+/// of `dst` past the result must come back unchanged.
+macro_rules! asm_proof {
+    ($name:ident, $n:literal, $out:literal, $spec:expr) => {
+        // The model's registers and dst are 16 limbs whatever the kernel
+        #[kani::proof]
+        #[kani::unwind(17)]
+        fn $name() {
+            const _: () = assert!(17 == unwind(16, 16));
+            let mut m = super::x86_64::Machine {
+                reg: limbs(&kani::any::<[u8; 128]>()),
+                cf: kani::any(),
+                a: [0; 8],
+                b: [0; 8],
+                dst: limbs(&kani::any::<[u8; 128]>()),
+            };
+            let a: [u64; $n] = limbs(&kani::any::<[u8; 8 * $n]>());
+            let b: [u64; $n] = limbs(&kani::any::<[u8; 8 * $n]>());
+            m.a[..$n].copy_from_slice(&a);
+            m.b[..$n].copy_from_slice(&b);
+            let before = m.dst;
+            super::x86_64::$name(&mut m);
+
+            let expected: [u64; $out] = $spec(&a, &b);
+            assert_eq!(m.dst[..$out], expected);
+            assert_eq!(m.dst[$out..], before[$out..]);
+        }
+    };
+}
+
+/// `cargo kani --harness proofs::asm::` runs just these.
+mod asm {
+    use super::*;
+
+    asm_proof!(wrapping_add_256, 4, 4, spec_add::<4>);
+    asm_proof!(wrapping_add_512, 8, 8, spec_add::<8>);
+    asm_proof!(wrapping_sub_256, 4, 4, spec_wrapping_sub::<4>);
+    asm_proof!(wrapping_sub_512, 8, 8, spec_wrapping_sub::<8>);
+    asm_proof!(wrapping_mul_256, 4, 4, spec_mul::<4, 4>);
+    asm_proof!(wrapping_mul_512, 8, 8, spec_mul::<8, 8>);
+    asm_proof!(widening_mul_256, 4, 8, spec_mul::<4, 8>);
+    asm_proof!(widening_mul_512, 8, 16, spec_mul::<8, 16>);
+}
@@ -0,0 +1,86 @@
+#include <stdbool.h>
+#include <stdint.h>
+#include <string.h>
+
+// Portable C twins of the asm kernels, built with the c-reference feature.
+// Elements are copied in and out with memcpy, so operands need no alignment,
+// and the limb loops have constant trip counts for the compiler to unroll.
+
+typedef unsigned __int128 u128;
+
+static inline void add_n(const uint64_t *a, const uint64_t *b, uint64_t *dst, int n) {
+  uint64_t carry = 0;
+  for (int i = 0; i < n; i++) {
+    u128 t = (u128)a[i] + b[i] + carry;
+    dst[i] = (uint64_t)t;
+    carry = (uint64_t)(t >> 64);
+  }
+}
+
+static inline uint64_t sub_n(const uint64_t *a, const uint64_t *b, uint64_t *dst, int n) {
+  uint64_t borrow = 0;
+  for (int i = 0; i < n; i++) {
+    u128 t = (u128)a[i] - b[i] - borrow;
+    dst[i] = (uint64_t)t;
+    borrow = (uint64_t)(t >> 64) & 1;
+  }
+  return borrow;
+}
+
+// Operand scanning, keeping the low `out` limbs of the product
+static inline void mul_n(const uint64_t *a, const uint64_t *b, uint64_t *dst, int n, int out) {
+  memset(dst, 0, out * 8);
+  for (int i = 0; i < n; i++) {
+    uint64_t carry = 0;
+    for (int j = 0; j < n && i + j < out; j++) {
+      u128 t = (u128)a[i] * b[j] + dst[i + j] + carry;
+      dst[i + j] = (uint64_t)t;
+      carry = (uint64_t)(t >> 64);
+    }
+    if (i + n < out) {
+      dst[i + n] = carry;
+    }
+  }
+}
+
+#define BINARY(name, n, out, body)                                                      \
+  void name##_c_impl(const uint8_t *a, const uint8_t *b, uint8_t *dst, uint64_t len) { \
+    for (uint64_t i = 0; i < len; i++) {                                                \
+      uint64_t x[n], y[n], z[out];                                                      \
+      memcpy(x, a + i * n * 8, n * 8);                                                  \
+      memcpy(y, b + i * n * 8, n * 8);                                                  \
+      body;                                                                             \
+      memcpy(dst + i * out * 8, z, out * 8);                                            \
+    }                                                                                   \
+  }
+
+BINARY(wrapping_add_256, 4, 4, add_n(x, y, z, 4))
+BINARY(wrapping_add_512, 8, 8, add_n(x, y, z, 8))
+BINARY(wrapping_sub_256, 4, 4, sub_n(x, y, z, 4))
+BINARY(wrapping_sub_512, 8, 8, sub_n(x, y, z, 8))
+BINARY(wrapping_mul_256, 4, 4, mul_n(x, y, z, 4, 4))
+BINARY(wrapping_mul_512, 8, 8, mul_n(x, y, z, 8, 8))
+BINARY(widening_mul_256, 4, 8, mul_n(x, y, z, 4, 8))
+BINARY(widening_mul_512, 8, 16, mul_n(x, y, z, 8, 16))
+
+void wrapping_sqr_256_c_impl(const uint8_t *a, uint8_t *dst, uint64_t len) {
+  wrapping_mul_256_c_impl(a, a, dst, len);
+}
+
+void widening_sqr_256_c_impl(const uint8_t *a, uint8_t *dst, uint64_t len) {
+  widening_mul_256_c_impl(a, a, dst, len);
+}
+
+bool msbc_256_c_impl(const uint8_t *a, const uint8_t *b) {
+  uint64_t x[4], y[4], z[4];
+  memcpy(x, a, 32);
+  memcpy(y, b, 32);
+  return sub_n(x, y, z, 4);
+}
+
+bool msbc_512_c_impl(const uint8_t *a, const uint8_t *b) {
+  uint64_t x[8], y[8], z[8];
+  memcpy(x, a, 64);
+  memcpy(y, b, 64);
+  return sub_n(x, y, z, 8);
+}
@@ -0,0 +1,72 @@
+//! C twins of the asm kernels from src/lib.c and src/reference.c, enabled by
+//! the `c-reference` feature and re-exported at the crate root with a `_c`
+//! suffix. Without the feature nothing is built with `cc`. They take the same
+//! packed operands as the contiguous asm kernels, so the two can be swapped
+//! in tests and benchmarks, and they build wherever a C compiler with
+//! `unsigned __int128` does.
+
+mod ffi {
+    extern "C" {
+        pub fn narrowing_right_shift_512_c_impl(
+            src: *const u64,
+            dst: *mut u64,
+            shift: u32,
+            len: usize,
+        );
+        pub fn wrapping_add_256_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_add_512_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_sub_256_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_sub_512_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_mul_256_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_mul_512_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn widening_mul_256_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn widening_mul_512_c_impl(a: *const u8, b: *const u8, dst: *mut u8, len: u64);
+        pub fn wrapping_sqr_256_c_impl(a: *const u8, dst: *mut u8, len: u64);
+        pub fn widening_sqr_256_c_impl(a: *const u8, dst: *mut u8, len: u64);
+        pub fn msbc_256_c_impl(a: *const u8, b: *const u8) -> bool;
+        pub fn msbc_512_c_impl(a: *const u8, b: *const u8) -> bool;
+    }
+}
+
+macro_rules! binary {
+    ($($name:ident => $impl:ident,)*) => {
+        $(
+            pub fn $name(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+                unsafe { ffi::$impl(a, b, dst, len as u64) }
+            }
+        )*
+    };
+}
+
+binary!(
+    wrapping_add_256_c => wrapping_add_256_c_impl,
+    wrapping_add_512_c => wrapping_add_512_c_impl,
+    wrapping_sub_256_c => wrapping_sub_256_c_impl,
+    wrapping_sub_512_c => wrapping_sub_512_c_impl,
+    wrapping_mul_256_c => wrapping_mul_256_c_impl,
+    wrapping_mul_512_c => wrapping_mul_512_c_impl,
+    widening_mul_256_c => widening_mul_256_c_impl,
+    widening_mul_512_c => widening_mul_512_c_impl,
+);
+
+/// Unlike the other C kernels, needs 8-byte aligned `src` and `dst`.
+pub fn narrowing_right_shift_512_c(src: *const u8, dst: *mut u8, shift: u32, len: usize) {
+    unsafe { ffi::narrowing_right_shift_512_c_impl(src as *const u64, dst as *mut u64, shift, len) }
+}
+
+pub fn wrapping_sqr_256_c(a: *const u8, dst: *mut u8, len: usize) {
+    unsafe { ffi::wrapping_sqr_256_c_impl(a, dst, len as u64) }
+}
+
+pub fn widening_sqr_256_c(a: *const u8, dst: *mut u8, len: usize) {
+    unsafe { ffi::widening_sqr_256_c_impl(a, dst, len as u64) }
+}
+
+pub fn msbc_256_c(a: *const u8, b: *const u8) -> bool {
+    unsafe { ffi::msbc_256_c_impl(a, b) }
+}
+
+/// Borrow out of `a - b` on 512-bit operands, which has no asm version.
+pub fn msbc_512_c(a: *const u8, b: *const u8) -> bool {
+    unsafe { ffi::msbc_512_c_impl(a, b) }
+}
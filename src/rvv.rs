@@ -0,0 +1,238 @@
+//! The RISC-V backend, for VMs running on RVV 1.0 hardware. Built when the
+//! target enables the V extension, e.g. `-C target-feature=+v`.
+//!
+//! The kernels are lane-parallel: `vl` elements are processed at once, with
+//! limb `k` of every element in one vector register, gathered by a strided
+//! load. Carries and borrows between limbs live in mask register v0 and go
+//! through `vadc`/`vmadc` and `vsbc`/`vmsbc`. Products use `vmul`/`vmulhu`,
+//! row by row as in the x86 kernels, with the row's carry in v6 and the
+//! partial sums in `dst`.
+
+use core::arch::asm;
+
+macro_rules! carry_chain {
+    ($name:ident, $op:literal, $carry:literal) => {
+        /// `LIMBS`-limb elements at arbitrary byte strides.
+        #[inline(always)]
+        unsafe fn $name<const LIMBS: usize>(
+            a: *const u8,
+            a_stride: isize,
+            b: *const u8,
+            b_stride: isize,
+            dst: *mut u8,
+            dst_stride: isize,
+            len: usize,
+        ) {
+            if len == 0 {
+                return;
+            }
+            asm!(
+                "2:",
+                "vsetvli {vl}, {len}, e64, m1, ta, ma",
+                "mv {pa}, {a}",
+                "mv {pb}, {b}",
+                "mv {pd}, {dst}",
+                "mv {k}, {limbs}",
+                "vmclr.m v0",
+                "3:",
+                "vlse64.v v1, ({pa}), {sa}",
+                "vlse64.v v2, ({pb}), {sb}",
+                concat!($op, " v3, v1, v2, v0"),
+                concat!($carry, " v4, v1, v2, v0"),
+                "vmmv.m v0, v4",
+                "vsse64.v v3, ({pd}), {sd}",
+                "addi {pa}, {pa}, 8",
+                "addi {pb}, {pb}, 8",
+                "addi {pd}, {pd}, 8",
+                "addi {k}, {k}, -1",
+                "bnez {k}, 3b",
+                "",
+                "mul {k}, {vl}, {sa}",
+                "add {a}, {a}, {k}",
+                "mul {k}, {vl}, {sb}",
+                "add {b}, {b}, {k}",
+                "mul {k}, {vl}, {sd}",
+                "add {dst}, {dst}, {k}",
+                "sub {len}, {len}, {vl}",
+                "bnez {len}, 2b",
+                a = inout(reg) a => _,
+                b = inout(reg) b => _,
+                dst = inout(reg) dst => _,
+                len = inout(reg) len => _,
+                sa = in(reg) a_stride,
+                sb = in(reg) b_stride,
+                sd = in(reg) dst_stride,
+                limbs = in(reg) LIMBS,
+                vl = out(reg) _,
+                pa = out(reg) _,
+                pb = out(reg) _,
+                pd = out(reg) _,
+                k = out(reg) _,
+                clobber_abi("C"),
+            );
+        }
+    };
+}
+
+carry_chain!(add, "vadc.vvm", "vmadc.vvm");
+carry_chain!(sub, "vsbc.vvm", "vmsbc.vvm");
+
+/// Low `OUT` limbs of the product of `LIMBS`-limb elements, so `OUT` is
+/// `LIMBS` for wrapping and `2 * LIMBS` for widening multiplication. Like
+/// the x86 kernels, `dst` must not overlap the operands.
+///
+/// Each step adds `lo(a_i * b_j)` and the row carry onto `dst[i + j]`, and
+/// `hi(a_i * b_j)` plus the two carries out becomes the next row carry,
+/// which cannot overflow. Row 0 starts from zero instead of reading `dst`.
+#[inline(always)]
+unsafe fn mul<const LIMBS: usize, const OUT: usize>(
+    a: *const u8,
+    a_stride: isize,
+    b: *const u8,
+    b_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    if len == 0 {
+        return;
+    }
+    asm!(
+        "2:",
+        "vsetvli {vl}, {len}, e64, m1, ta, ma",
+        "li {i}, 0",
+        "3:",
+        "slli {t}, {i}, 3",
+        "add {pd}, {a}, {t}",
+        "vlse64.v v1, ({pd}), {sa}",
+        "add {pd}, {dst}, {t}",
+        "mv {pb}, {b}",
+        "vmv.v.i v6, 0",
+        "li {j}, 0",
+        "4:",
+        "vlse64.v v2, ({pb}), {sb}",
+        "vmul.vv v3, v1, v2",
+        "vmulhu.vv v4, v1, v2",
+        "vmv.v.i v5, 0",
+        "beqz {i}, 5f",
+        "vlse64.v v5, ({pd}), {sd}",
+        "5:",
+        "vadd.vv v5, v5, v3",
+        "vmsltu.vv v0, v5, v3",
+        "vadc.vim v4, v4, 0, v0",
+        "vadd.vv v5, v5, v6",
+        "vmsltu.vv v0, v5, v6",
+        "vadc.vim v6, v4, 0, v0",
+        "vsse64.v v5, ({pd}), {sd}",
+        "addi {pb}, {pb}, 8",
+        "addi {pd}, {pd}, 8",
+        "addi {j}, {j}, 1",
+        "add {t}, {i}, {j}",
+        "bgeu {t}, {out}, 6f",
+        "bltu {j}, {limbs}, 4b",
+        // The whole row fit, its carry is limb i + limbs
+        "vsse64.v v6, ({pd}), {sd}",
+        "6:",
+        "addi {i}, {i}, 1",
+        "bltu {i}, {limbs}, 3b",
+        "",
+        "mul {t}, {vl}, {sa}",
+        "add {a}, {a}, {t}",
+        "mul {t}, {vl}, {sb}",
+        "add {b}, {b}, {t}",
+        "mul {t}, {vl}, {sd}",
+        "add {dst}, {dst}, {t}",
+        "sub {len}, {len}, {vl}",
+        "bnez {len}, 2b",
+        a = inout(reg) a => _,
+        b = inout(reg) b => _,
+        dst = inout(reg) dst => _,
+        len = inout(reg) len => _,
+        sa = in(reg) a_stride,
+        sb = in(reg) b_stride,
+        sd = in(reg) dst_stride,
+        limbs = in(reg) LIMBS,
+        out = in(reg) OUT,
+        vl = out(reg) _,
+        i = out(reg) _,
+        j = out(reg) _,
+        t = out(reg) _,
+        pb = out(reg) _,
+        pd = out(reg) _,
+        clobber_abi("C"),
+    );
+}
+
+macro_rules! binary {
+    ($name:ident, $strided:ident, $n:expr, $m:expr, $kernel:ident $(, $out:expr)?) => {
+        #[inline(never)]
+        pub fn $name(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+            $strided(a, 8 * $n, b, 8 * $n, dst, 8 * $m, len);
+        }
+
+        binary!($strided, $n, $kernel $(, $out)?);
+    };
+    ($strided:ident, $n:expr, $kernel:ident $(, $out:expr)?) => {
+        #[inline(never)]
+        pub fn $strided(
+            a: *const u8,
+            a_stride: isize,
+            b: *const u8,
+            b_stride: isize,
+            dst: *mut u8,
+            dst_stride: isize,
+            len: usize,
+        ) {
+            unsafe { $kernel::<$n $(, $out)?>(a, a_stride, b, b_stride, dst, dst_stride, len) }
+        }
+    };
+}
+
+binary!(wrapping_add_256, wrapping_add_256_strided, 4, 4, add);
+binary!(wrapping_add_512, wrapping_add_512_strided, 8, 8, add);
+binary!(wrapping_sub_256, wrapping_sub_256_strided, 4, 4, sub);
+binary!(wrapping_sub_512, wrapping_sub_512_strided, 8, 8, sub);
+binary!(wrapping_mul_256, wrapping_mul_256_strided, 4, 4, mul, 4);
+binary!(wrapping_mul_512, wrapping_mul_512_strided, 8, 8, mul, 8);
+binary!(widening_mul_256_strided, 4, mul, 8);
+binary!(widening_mul_512, widening_mul_512_strided, 8, 16, mul, 16);
+
+#[inline(always)]
+pub(crate) unsafe fn widening_mul_256_impl(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    mul::<4, 8>(a, 32, b, 32, dst, 64, len);
+}
+
+#[inline(never)]
+pub fn widening_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, a, 32, dst, 64, len);
+}
+
+#[inline(never)]
+pub fn widening_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    widening_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    wrapping_mul_256(a, a, dst, len);
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    wrapping_mul_256_strided(a, a_stride, a, a_stride, dst, dst_stride, len);
+}
+
+// A single comparison has nothing to vectorize
+pub use crate::portable::msbc_256;
@@ -0,0 +1,212 @@
+//! AVX2 kernels.
+//!
+//! Multiplication runs 4 lanes at once using 32-bit digits. `vpmuludq`
+//! multiplies the low 32 bits of each 64-bit lane, so every lane holds one
+//! digit of a different element. Partial products are split into their low
+//! and high halves and summed per column, which leaves enough headroom that
+//! carries only need resolving once at the end.
+//!
+//! Addition and subtraction keep one element per register and resolve
+//! carries from comparison masks instead.
+
+use core::arch::x86_64::*;
+
+pub const LANES: usize = 4;
+
+// Turns 4 rows of 4 limbs into 4 columns of 4 lanes, and back again.
+#[inline(always)]
+unsafe fn transpose(r: [__m256i; 4]) -> [__m256i; 4] {
+    let t0 = _mm256_unpacklo_epi64(r[0], r[1]);
+    let t1 = _mm256_unpackhi_epi64(r[0], r[1]);
+    let t2 = _mm256_unpacklo_epi64(r[2], r[3]);
+    let t3 = _mm256_unpackhi_epi64(r[2], r[3]);
+    [
+        _mm256_permute2x128_si256(t0, t2, 0x20),
+        _mm256_permute2x128_si256(t1, t3, 0x20),
+        _mm256_permute2x128_si256(t0, t2, 0x31),
+        _mm256_permute2x128_si256(t1, t3, 0x31),
+    ]
+}
+
+#[inline(always)]
+unsafe fn load_digits(src: *const u8, stride: usize) -> [__m256i; 8] {
+    let limbs = transpose([
+        _mm256_loadu_si256(src as *const __m256i),
+        _mm256_loadu_si256(src.add(stride) as *const __m256i),
+        _mm256_loadu_si256(src.add(stride * 2) as *const __m256i),
+        _mm256_loadu_si256(src.add(stride * 3) as *const __m256i),
+    ]);
+    // vpmuludq ignores the upper half of each lane, so even digits need no masking
+    [
+        limbs[0],
+        _mm256_srli_epi64(limbs[0], 32),
+        limbs[1],
+        _mm256_srli_epi64(limbs[1], 32),
+        limbs[2],
+        _mm256_srli_epi64(limbs[2], 32),
+        limbs[3],
+        _mm256_srli_epi64(limbs[3], 32),
+    ]
+}
+
+#[inline(always)]
+unsafe fn store_limbs(dst: *mut u8, stride: usize, limbs: [__m256i; 4]) {
+    let rows = transpose(limbs);
+    _mm256_storeu_si256(dst as *mut __m256i, rows[0]);
+    _mm256_storeu_si256(dst.add(stride) as *mut __m256i, rows[1]);
+    _mm256_storeu_si256(dst.add(stride * 2) as *mut __m256i, rows[2]);
+    _mm256_storeu_si256(dst.add(stride * 3) as *mut __m256i, rows[3]);
+}
+
+// Resolves carries between 32-bit columns and packs pairs of them into limbs.
+#[inline(always)]
+unsafe fn normalize<const C: usize, const L: usize>(cols: &[__m256i; C]) -> [__m256i; L] {
+    let mask = _mm256_set1_epi64x(0xffff_ffff);
+    let mut carry = _mm256_setzero_si256();
+    let mut limbs = [_mm256_setzero_si256(); L];
+    for k in 0..C {
+        let t = _mm256_add_epi64(cols[k], carry);
+        carry = _mm256_srli_epi64(t, 32);
+        if k % 2 == 0 {
+            limbs[k / 2] = _mm256_and_si256(t, mask);
+        } else {
+            limbs[k / 2] = _mm256_or_si256(limbs[k / 2], _mm256_slli_epi64(t, 32));
+        }
+    }
+    limbs
+}
+
+/// Multiplies `len / LANES` groups of elements, returning how many elements
+/// were processed. The caller handles the remaining tail.
+#[target_feature(enable = "avx2")]
+pub unsafe fn widening_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let mask = _mm256_set1_epi64x(0xffff_ffff);
+    let groups = len / LANES;
+
+    for g in 0..groups {
+        let x = load_digits(a.add(g * LANES * 32), 32);
+        let y = load_digits(b.add(g * LANES * 32), 32);
+
+        let mut cols = [_mm256_setzero_si256(); 16];
+        for i in 0..8 {
+            for j in 0..8 {
+                let p = _mm256_mul_epu32(x[i], y[j]);
+                cols[i + j] = _mm256_add_epi64(cols[i + j], _mm256_and_si256(p, mask));
+                cols[i + j + 1] = _mm256_add_epi64(cols[i + j + 1], _mm256_srli_epi64(p, 32));
+            }
+        }
+
+        let limbs: [__m256i; 8] = normalize(&cols);
+        let dst = dst.add(g * LANES * 64);
+        store_limbs(dst, 64, [limbs[0], limbs[1], limbs[2], limbs[3]]);
+        store_limbs(dst.add(32), 64, [limbs[4], limbs[5], limbs[6], limbs[7]]);
+    }
+    groups * LANES
+}
+
+/// Low-half counterpart of [`widening_mul_256`], skipping columns above bit 256.
+#[target_feature(enable = "avx2")]
+pub unsafe fn wrapping_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let mask = _mm256_set1_epi64x(0xffff_ffff);
+    let groups = len / LANES;
+
+    for g in 0..groups {
+        let x = load_digits(a.add(g * LANES * 32), 32);
+        let y = load_digits(b.add(g * LANES * 32), 32);
+
+        let mut cols = [_mm256_setzero_si256(); 8];
+        for i in 0..8 {
+            for j in 0..8 - i {
+                let p = _mm256_mul_epu32(x[i], y[j]);
+                cols[i + j] = _mm256_add_epi64(cols[i + j], _mm256_and_si256(p, mask));
+                if i + j + 1 < 8 {
+                    cols[i + j + 1] = _mm256_add_epi64(cols[i + j + 1], _mm256_srli_epi64(p, 32));
+                }
+            }
+        }
+
+        let limbs: [__m256i; 4] = normalize(&cols);
+        store_limbs(dst.add(g * LANES * 32), 32, limbs);
+    }
+    groups * LANES
+}
+
+// AVX2 has no unsigned 64-bit compare, so flip the sign bits and compare
+// signed instead. Returns one bit per limb.
+#[inline(always)]
+unsafe fn lt_mask(x: __m256i, y: __m256i) -> u8 {
+    let sign = _mm256_set1_epi64x(i64::MIN);
+    let lt = _mm256_cmpgt_epi64(_mm256_xor_si256(y, sign), _mm256_xor_si256(x, sign));
+    _mm256_movemask_pd(_mm256_castsi256_pd(lt)) as u8
+}
+
+#[inline(always)]
+unsafe fn eq_mask(x: __m256i, y: __m256i) -> u8 {
+    _mm256_movemask_pd(_mm256_castsi256_pd(_mm256_cmpeq_epi64(x, y))) as u8
+}
+
+// Expands the low 4 bits of `mask` into all-ones limbs.
+#[inline(always)]
+unsafe fn expand(mask: u8) -> __m256i {
+    let bits = _mm256_setr_epi64x(1, 2, 4, 8);
+    let m = _mm256_and_si256(_mm256_set1_epi64x(mask as i64), bits);
+    _mm256_cmpeq_epi64(m, bits)
+}
+
+// See `simd::avx512` for how the generate/propagate masks resolve carries.
+#[inline(always)]
+fn carry_in(g: u8, p: u8, tops: u8) -> u8 {
+    let p = p & !tops;
+    (g << 1).wrapping_add(p) ^ p
+}
+
+/// Adds 256-bit elements one per register, returning how many elements were
+/// processed.
+#[target_feature(enable = "avx2")]
+pub unsafe fn wrapping_add_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let ones = _mm256_set1_epi64x(-1);
+    for i in 0..len {
+        let x = _mm256_loadu_si256(a.add(i * 32) as *const __m256i);
+        let y = _mm256_loadu_si256(b.add(i * 32) as *const __m256i);
+        let s = _mm256_add_epi64(x, y);
+        let c = carry_in(lt_mask(s, x), eq_mask(s, ones), 0x08);
+        // Lanes receiving a carry are all-ones, so subtracting adds 1
+        let s = _mm256_sub_epi64(s, expand(c));
+        _mm256_storeu_si256(dst.add(i * 32) as *mut __m256i, s);
+    }
+    len
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn wrapping_sub_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    for i in 0..len {
+        let x = _mm256_loadu_si256(a.add(i * 32) as *const __m256i);
+        let y = _mm256_loadu_si256(b.add(i * 32) as *const __m256i);
+        let d = _mm256_sub_epi64(x, y);
+        let c = carry_in(lt_mask(x, y), eq_mask(d, _mm256_setzero_si256()), 0x08);
+        let d = _mm256_add_epi64(d, expand(c));
+        _mm256_storeu_si256(dst.add(i * 32) as *mut __m256i, d);
+    }
+    len
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn wrapping_add_512(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let ones = _mm256_set1_epi64x(-1);
+    for i in 0..len {
+        let x0 = _mm256_loadu_si256(a.add(i * 64) as *const __m256i);
+        let x1 = _mm256_loadu_si256(a.add(i * 64 + 32) as *const __m256i);
+        let y0 = _mm256_loadu_si256(b.add(i * 64) as *const __m256i);
+        let y1 = _mm256_loadu_si256(b.add(i * 64 + 32) as *const __m256i);
+        let s0 = _mm256_add_epi64(x0, y0);
+        let s1 = _mm256_add_epi64(x1, y1);
+        let g = lt_mask(s0, x0) | (lt_mask(s1, x1) << 4);
+        let p = eq_mask(s0, ones) | (eq_mask(s1, ones) << 4);
+        let c = carry_in(g, p, 0x80);
+        let s0 = _mm256_sub_epi64(s0, expand(c));
+        let s1 = _mm256_sub_epi64(s1, expand(c >> 4));
+        _mm256_storeu_si256(dst.add(i * 64) as *mut __m256i, s0);
+        _mm256_storeu_si256(dst.add(i * 64 + 32) as *mut __m256i, s1);
+    }
+    len
+}
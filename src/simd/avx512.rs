@@ -0,0 +1,68 @@
+//! Add and subtract with AVX-512F, keeping elements in their natural layout.
+//!
+//! All limbs are added at once, then carries are resolved from two masks:
+//! `g` for limbs that overflowed and `p` for limbs that would pass an
+//! incoming carry on. `((g << 1) + p) ^ p` gives the limbs that receive a
+//! carry, exactly as a ripple through the `adc` chain would. Bits at element
+//! boundaries are cleared so carries never leak into the next element.
+
+use core::arch::x86_64::*;
+
+// `g` and `p` can never both be set for the same limb, which is what keeps
+// the mask addition from carrying across a boundary once the top limb's `p`
+// bit is cleared.
+#[inline(always)]
+fn carry_in(g: u8, p: u8, starts: u8, tops: u8) -> u8 {
+    let p = p & !tops;
+    ((g << 1) & !starts).wrapping_add(p) ^ p
+}
+
+#[inline(always)]
+unsafe fn add(a: *const u8, b: *const u8, dst: *mut u8, starts: u8, tops: u8) {
+    let x = _mm512_loadu_si512(a as *const _);
+    let y = _mm512_loadu_si512(b as *const _);
+    let s = _mm512_add_epi64(x, y);
+    let g = _mm512_cmplt_epu64_mask(s, x);
+    let p = _mm512_cmpeq_epi64_mask(s, _mm512_set1_epi64(-1));
+    let c = carry_in(g, p, starts, tops);
+    let s = _mm512_mask_add_epi64(s, c, s, _mm512_set1_epi64(1));
+    _mm512_storeu_si512(dst as *mut _, s);
+}
+
+#[inline(always)]
+unsafe fn sub(a: *const u8, b: *const u8, dst: *mut u8, starts: u8, tops: u8) {
+    let x = _mm512_loadu_si512(a as *const _);
+    let y = _mm512_loadu_si512(b as *const _);
+    let d = _mm512_sub_epi64(x, y);
+    let g = _mm512_cmplt_epu64_mask(x, y);
+    let p = _mm512_cmpeq_epi64_mask(d, _mm512_setzero_si512());
+    let c = carry_in(g, p, starts, tops);
+    let d = _mm512_mask_sub_epi64(d, c, d, _mm512_set1_epi64(1));
+    _mm512_storeu_si512(dst as *mut _, d);
+}
+
+/// Adds pairs of 256-bit elements, returning how many elements were
+/// processed. The caller handles the remaining tail.
+#[target_feature(enable = "avx512f")]
+pub unsafe fn wrapping_add_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    for i in 0..len / 2 {
+        add(a.add(i * 64), b.add(i * 64), dst.add(i * 64), 0x11, 0x88);
+    }
+    len / 2 * 2
+}
+
+#[target_feature(enable = "avx512f")]
+pub unsafe fn wrapping_sub_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    for i in 0..len / 2 {
+        sub(a.add(i * 64), b.add(i * 64), dst.add(i * 64), 0x11, 0x88);
+    }
+    len / 2 * 2
+}
+
+#[target_feature(enable = "avx512f")]
+pub unsafe fn wrapping_add_512(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    for i in 0..len {
+        add(a.add(i * 64), b.add(i * 64), dst.add(i * 64), 0x01, 0x80);
+    }
+    len
+}
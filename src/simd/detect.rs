@@ -0,0 +1,72 @@
+//! CPU feature detection without std.
+//!
+//! `is_x86_feature_detected!` lives in std, so `no_std` builds ask cpuid
+//! directly. A feature only counts if the OS also saves the registers it
+//! uses, which XCR0 reports. Like std, the answer is cached after the first
+//! probe, so the dispatchers can ask on every call.
+
+use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+// XCR0 state components: SSE and AVX, then the three AVX-512 ones.
+const XCR0_AVX: u64 = 0b110;
+const XCR0_AVX512: u64 = 0b1110_0110;
+
+const AVX2: u8 = 1;
+const AVX512F: u8 = 2;
+const AVX512IFMA: u8 = 4;
+/// Set in the cache once it holds the probed bits.
+const INITIALIZED: u8 = 0x80;
+
+static FEATURES: AtomicU8 = AtomicU8::new(0);
+
+pub fn detected(feature: &str) -> bool {
+    let bit = match feature {
+        "avx2" => AVX2,
+        "avx512f" => AVX512F,
+        "avx512ifma" => AVX512IFMA,
+        _ => return false,
+    };
+    features() & bit != 0
+}
+
+/// Racing first calls probe more than once and store the same value, so
+/// relaxed ordering is enough.
+fn features() -> u8 {
+    let cached = FEATURES.load(Ordering::Relaxed);
+    if cached & INITIALIZED != 0 {
+        return cached;
+    }
+    let probed = probe() | INITIALIZED;
+    FEATURES.store(probed, Ordering::Relaxed);
+    probed
+}
+
+fn probe() -> u8 {
+    let (ebx, xcr0) = unsafe { leaf7_and_xcr0() };
+    let mut features = 0;
+    for (feature, bit, state) in [
+        (AVX2, 5, XCR0_AVX),
+        (AVX512F, 16, XCR0_AVX512),
+        (AVX512IFMA, 21, XCR0_AVX512),
+    ] {
+        if ebx & (1 << bit) != 0 && xcr0 & state == state {
+            features |= feature;
+        }
+    }
+    features
+}
+
+unsafe fn leaf7_and_xcr0() -> (u32, u64) {
+    if __cpuid(0).eax < 7 {
+        return (0, 0);
+    }
+    let osxsave = __cpuid(1).ecx & (1 << 27) != 0;
+    let xcr0 = if osxsave { xgetbv() } else { 0 };
+    (__cpuid_count(7, 0).ebx, xcr0)
+}
+
+#[target_feature(enable = "xsave")]
+unsafe fn xgetbv() -> u64 {
+    _xgetbv(0)
+}
@@ -0,0 +1,147 @@
+//! 8-lane multiplication with AVX-512 IFMA, using 52-bit digits.
+//!
+//! `vpmadd52luq`/`vpmadd52huq` accumulate the low and high 52 bits of a
+//! 52x52-bit product directly into 64-bit lanes, leaving 12 bits of headroom
+//! per column for the at most 10 terms a 5-digit schoolbook produces.
+
+use core::arch::x86_64::*;
+
+pub const LANES: usize = 8;
+
+const MASK52: i64 = (1 << 52) - 1;
+
+#[inline(always)]
+unsafe fn load_digits(src: *const u8, stride: i64) -> [__m512i; 5] {
+    let offsets = _mm512_setr_epi64(
+        0,
+        stride,
+        stride * 2,
+        stride * 3,
+        stride * 4,
+        stride * 5,
+        stride * 6,
+        stride * 7,
+    );
+    let x0 = _mm512_i64gather_epi64::<1>(offsets, src as *const i64);
+    let x1 = _mm512_i64gather_epi64::<1>(offsets, src.add(8) as *const i64);
+    let x2 = _mm512_i64gather_epi64::<1>(offsets, src.add(16) as *const i64);
+    let x3 = _mm512_i64gather_epi64::<1>(offsets, src.add(24) as *const i64);
+
+    let mask = _mm512_set1_epi64(MASK52);
+    [
+        _mm512_and_si512(x0, mask),
+        _mm512_and_si512(
+            _mm512_or_si512(_mm512_srli_epi64(x0, 52), _mm512_slli_epi64(x1, 12)),
+            mask,
+        ),
+        _mm512_and_si512(
+            _mm512_or_si512(_mm512_srli_epi64(x1, 40), _mm512_slli_epi64(x2, 24)),
+            mask,
+        ),
+        _mm512_and_si512(
+            _mm512_or_si512(_mm512_srli_epi64(x2, 28), _mm512_slli_epi64(x3, 36)),
+            mask,
+        ),
+        _mm512_srli_epi64(x3, 16),
+    ]
+}
+
+#[inline(always)]
+unsafe fn normalize<const C: usize>(cols: &[__m512i; C]) -> [__m512i; C] {
+    let mask = _mm512_set1_epi64(MASK52);
+    let mut carry = _mm512_setzero_si512();
+    let mut digits = [_mm512_setzero_si512(); C];
+    for k in 0..C {
+        let t = _mm512_add_epi64(cols[k], carry);
+        carry = _mm512_srli_epi64(t, 52);
+        digits[k] = _mm512_and_si512(t, mask);
+    }
+    digits
+}
+
+#[inline(always)]
+unsafe fn join<const LO: u32, const HI: u32>(lo: __m512i, hi: __m512i) -> __m512i {
+    _mm512_or_si512(_mm512_srli_epi64::<LO>(lo), _mm512_slli_epi64::<HI>(hi))
+}
+
+#[inline(always)]
+unsafe fn scatter(dst: *mut u8, stride: i64, limbs: &[__m512i]) {
+    let offsets = _mm512_setr_epi64(
+        0,
+        stride,
+        stride * 2,
+        stride * 3,
+        stride * 4,
+        stride * 5,
+        stride * 6,
+        stride * 7,
+    );
+    for (k, limb) in limbs.iter().enumerate() {
+        _mm512_i64scatter_epi64::<1>(dst.add(k * 8) as *mut i64, offsets, *limb);
+    }
+}
+
+/// Multiplies `len / LANES` groups of elements, returning how many elements
+/// were processed. The caller handles the remaining tail.
+#[target_feature(enable = "avx512f,avx512ifma")]
+pub unsafe fn widening_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let groups = len / LANES;
+
+    for g in 0..groups {
+        let x = load_digits(a.add(g * LANES * 32), 32);
+        let y = load_digits(b.add(g * LANES * 32), 32);
+
+        let mut cols = [_mm512_setzero_si512(); 10];
+        for i in 0..5 {
+            for j in 0..5 {
+                cols[i + j] = _mm512_madd52lo_epu64(cols[i + j], x[i], y[j]);
+                cols[i + j + 1] = _mm512_madd52hi_epu64(cols[i + j + 1], x[i], y[j]);
+            }
+        }
+
+        let r = normalize(&cols);
+        let limbs = [
+            join::<0, 52>(r[0], r[1]),
+            join::<12, 40>(r[1], r[2]),
+            join::<24, 28>(r[2], r[3]),
+            join::<36, 16>(r[3], r[4]),
+            _mm512_or_si512(join::<48, 4>(r[4], r[5]), _mm512_slli_epi64(r[6], 56)),
+            join::<8, 44>(r[6], r[7]),
+            join::<20, 32>(r[7], r[8]),
+            join::<32, 20>(r[8], r[9]),
+        ];
+        scatter(dst.add(g * LANES * 64), 64, &limbs);
+    }
+    groups * LANES
+}
+
+/// Low-half counterpart of [`widening_mul_256`], skipping digits above bit 260.
+#[target_feature(enable = "avx512f,avx512ifma")]
+pub unsafe fn wrapping_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) -> usize {
+    let groups = len / LANES;
+
+    for g in 0..groups {
+        let x = load_digits(a.add(g * LANES * 32), 32);
+        let y = load_digits(b.add(g * LANES * 32), 32);
+
+        let mut cols = [_mm512_setzero_si512(); 5];
+        for i in 0..5 {
+            for j in 0..5 - i {
+                cols[i + j] = _mm512_madd52lo_epu64(cols[i + j], x[i], y[j]);
+                if i + j + 1 < 5 {
+                    cols[i + j + 1] = _mm512_madd52hi_epu64(cols[i + j + 1], x[i], y[j]);
+                }
+            }
+        }
+
+        let r = normalize(&cols);
+        let limbs = [
+            join::<0, 52>(r[0], r[1]),
+            join::<12, 40>(r[1], r[2]),
+            join::<24, 28>(r[2], r[3]),
+            join::<36, 16>(r[3], r[4]),
+        ];
+        scatter(dst.add(g * LANES * 32), 32, &limbs);
+    }
+    groups * LANES
+}
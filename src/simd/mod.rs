@@ -0,0 +1,193 @@
+//! Lane-parallel kernels for large batches, selected at runtime.
+//!
+//! The entry points here have the same signatures as the scalar kernels in
+//! the crate root. They hand whole groups of elements to the widest vector
+//! backend the running CPU supports and finish the tail with the scalar asm.
+
+mod avx2;
+mod avx512;
+#[cfg(not(feature = "std"))]
+mod detect;
+mod ifma;
+
+// Runtime detection goes through std when it is there, for its cache.
+#[cfg(feature = "std")]
+macro_rules! detected {
+    ($feature:tt) => {
+        std::is_x86_feature_detected!($feature)
+    };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! detected {
+    ($feature:tt) => {
+        detect::detected($feature)
+    };
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Backend {
+    Scalar,
+    Avx2,
+    /// AVX-512F. Multiplication has no kernel here and runs on the scalar path.
+    Avx512,
+    /// AVX-512F with IFMA. Implies [`Backend::Avx512`] for addition.
+    Ifma,
+}
+
+/// Below this many elements the gather/transpose overhead is not worth it.
+pub const MUL_THRESHOLD: usize = 16;
+
+/// Below this many elements the scalar `adc` chain wins.
+pub const ADD_THRESHOLD: usize = 8;
+
+/// Picks the backend used for multiplication of large batches.
+///
+/// The AVX2 backend needs 64 `vpmuludq` per 4 products plus transposes and
+/// loses to the scalar `mul` chain, so it is never chosen automatically and
+/// only reachable through [`widening_mul_256_with`].
+pub fn mul_backend() -> Backend {
+    if detected!("avx512f") && detected!("avx512ifma") {
+        Backend::Ifma
+    } else {
+        Backend::Scalar
+    }
+}
+
+pub fn widening_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    let backend = if len >= MUL_THRESHOLD {
+        mul_backend()
+    } else {
+        Backend::Scalar
+    };
+    widening_mul_256_with(backend, a, b, dst, len);
+}
+
+pub fn wrapping_mul_256(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    let backend = if len >= MUL_THRESHOLD {
+        mul_backend()
+    } else {
+        Backend::Scalar
+    };
+    wrapping_mul_256_with(backend, a, b, dst, len);
+}
+
+/// Runs the widening multiply on an explicit backend.
+///
+/// # Panics
+///
+/// Panics if the running CPU does not support `backend`.
+pub fn widening_mul_256_with(
+    backend: Backend,
+    a: *const u8,
+    b: *const u8,
+    dst: *mut u8,
+    len: usize,
+) {
+    let done = match backend {
+        Backend::Scalar | Backend::Avx512 => 0,
+        Backend::Avx2 => {
+            assert!(detected!("avx2"));
+            unsafe { avx2::widening_mul_256(a, b, dst, len) }
+        }
+        Backend::Ifma => {
+            assert!(detected!("avx512f") && detected!("avx512ifma"));
+            unsafe { ifma::widening_mul_256(a, b, dst, len) }
+        }
+    };
+    crate::widening_mul_256_strided(
+        a.wrapping_add(done * 32),
+        32,
+        b.wrapping_add(done * 32),
+        32,
+        dst.wrapping_add(done * 64),
+        64,
+        len - done,
+    );
+}
+
+/// Runs the wrapping multiply on an explicit backend.
+///
+/// # Panics
+///
+/// Panics if the running CPU does not support `backend`.
+pub fn wrapping_mul_256_with(
+    backend: Backend,
+    a: *const u8,
+    b: *const u8,
+    dst: *mut u8,
+    len: usize,
+) {
+    let done = match backend {
+        Backend::Scalar | Backend::Avx512 => 0,
+        Backend::Avx2 => {
+            assert!(detected!("avx2"));
+            unsafe { avx2::wrapping_mul_256(a, b, dst, len) }
+        }
+        Backend::Ifma => {
+            assert!(detected!("avx512f") && detected!("avx512ifma"));
+            unsafe { ifma::wrapping_mul_256(a, b, dst, len) }
+        }
+    };
+    crate::wrapping_mul_256(
+        a.wrapping_add(done * 32),
+        b.wrapping_add(done * 32),
+        dst.wrapping_add(done * 32),
+        len - done,
+    );
+}
+
+/// Picks the backend used for addition and subtraction of large batches.
+///
+/// Like multiplication, the AVX2 kernels lose to the scalar chain (mostly
+/// from the missing unsigned compare and mask registers), so only AVX-512
+/// is chosen automatically.
+pub fn add_backend() -> Backend {
+    if detected!("avx512f") {
+        Backend::Avx512
+    } else {
+        Backend::Scalar
+    }
+}
+
+macro_rules! add_dispatch {
+    ($name:ident, $name_with:ident, $bytes:expr) => {
+        pub fn $name(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+            let backend = if len >= ADD_THRESHOLD {
+                add_backend()
+            } else {
+                Backend::Scalar
+            };
+            $name_with(backend, a, b, dst, len);
+        }
+
+        /// Runs the kernel on an explicit backend.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the running CPU does not support `backend`.
+        pub fn $name_with(backend: Backend, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+            let done = match backend {
+                Backend::Scalar => 0,
+                Backend::Avx2 => {
+                    assert!(detected!("avx2"));
+                    unsafe { avx2::$name(a, b, dst, len) }
+                }
+                Backend::Avx512 | Backend::Ifma => {
+                    assert!(detected!("avx512f"));
+                    unsafe { avx512::$name(a, b, dst, len) }
+                }
+            };
+            crate::$name(
+                a.wrapping_add(done * $bytes),
+                b.wrapping_add(done * $bytes),
+                dst.wrapping_add(done * $bytes),
+                len - done,
+            );
+        }
+    };
+}
+
+add_dispatch!(wrapping_add_256, wrapping_add_256_with, 32);
+add_dispatch!(wrapping_sub_256, wrapping_sub_256_with, 32);
+add_dispatch!(wrapping_add_512, wrapping_add_512_with, 64);
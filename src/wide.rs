@@ -0,0 +1,208 @@
+//! Multiplication and squaring for 512 and 1024-bit lanes.
+//!
+//! The default kernels are Karatsuba built on the half-width batch kernels,
+//! bottoming out in the asm `widening_mul_256`, with the operands split and
+//! recombined a chunk at a time so the half-width calls stay batched. The
+//! `_comba` variants are plain product-scanning schoolbook on `u64` limbs,
+//! kept as the baseline for the benchmarks.
+
+use crate::lane::{self, Lane, U1024, U2048, U256, U512};
+use crate::limb;
+
+const CHUNK: usize = 16;
+
+/// Writes `|x - y|` into `dst`, returning an all ones mask if `x < y`.
+#[inline(always)]
+fn abs_diff(x: &[u64], y: &[u64], dst: &mut [u64]) -> u64 {
+    dst.copy_from_slice(x);
+    let mask = limb::sub_assign(dst, y).wrapping_neg();
+    for d in dst.iter_mut() {
+        *d ^= mask;
+    }
+    limb::add_assign(dst, &[mask & 1]);
+    mask
+}
+
+macro_rules! karatsuba {
+    ($widening:ident, $wrapping:ident, $half:ident, $full:ident, $double:ident,
+     $half_widening:path, $half_wrapping:path) => {
+        /// Karatsuba on halves: `(a0 - a1) * (b1 - b0) + a0 * b0 + a1 * b1`
+        /// gives the middle term, and the subtractive form keeps every
+        /// operand at half width.
+        pub fn $widening(a: &[$full], b: &[$full], dst: &mut [$double]) {
+            assert!(a.len() == dst.len() && b.len() == dst.len());
+            const H: usize = <$half as Lane>::LIMBS;
+            let mut a0 = [$half::ZERO; CHUNK];
+            let mut a1 = [$half::ZERO; CHUNK];
+            let mut b0 = [$half::ZERO; CHUNK];
+            let mut b1 = [$half::ZERO; CHUNK];
+            let mut da = [$half::ZERO; CHUNK];
+            let mut db = [$half::ZERO; CHUNK];
+            let mut neg = [0u64; CHUNK];
+            let mut z0 = [$full::ZERO; CHUNK];
+            let mut z2 = [$full::ZERO; CHUNK];
+            let mut zm = [$full::ZERO; CHUNK];
+
+            for start in (0..dst.len()).step_by(CHUNK) {
+                let n = core::cmp::min(CHUNK, dst.len() - start);
+                for i in 0..n {
+                    let (x, y) = (a[start + i].limbs(), b[start + i].limbs());
+                    a0[i].limbs_mut().copy_from_slice(&x[..H]);
+                    a1[i].limbs_mut().copy_from_slice(&x[H..]);
+                    b0[i].limbs_mut().copy_from_slice(&y[..H]);
+                    b1[i].limbs_mut().copy_from_slice(&y[H..]);
+                    neg[i] = abs_diff(&x[..H], &x[H..], da[i].limbs_mut())
+                        ^ abs_diff(&y[H..], &y[..H], db[i].limbs_mut());
+                }
+                $half_widening(&a0[..n], &b0[..n], &mut z0[..n]);
+                $half_widening(&a1[..n], &b1[..n], &mut z2[..n]);
+                $half_widening(&da[..n], &db[..n], &mut zm[..n]);
+
+                for i in 0..n {
+                    let d = dst[start + i].limbs_mut();
+                    let (lo, hi) = (z0[i].limbs(), z2[i].limbs());
+                    d[..2 * H].copy_from_slice(lo);
+                    d[2 * H..].copy_from_slice(hi);
+                    limb::add_assign(&mut d[H..], lo);
+                    limb::add_assign(&mut d[H..], hi);
+
+                    // Add the middle product, negated in two's complement
+                    // when exactly one of the differences was negative.
+                    let mut middle = [neg[i]; 3 * H];
+                    for (m, z) in middle.iter_mut().zip(zm[i].limbs()) {
+                        *m = z ^ neg[i];
+                    }
+                    limb::add_assign(&mut d[H..], &middle);
+                    limb::add_assign(&mut d[H..], &[neg[i] & 1]);
+                }
+            }
+        }
+
+        /// Only the low half of the middle term survives truncation, so two
+        /// wrapping half products are as cheap as the Karatsuba one here.
+        pub fn $wrapping(a: &[$full], b: &[$full], dst: &mut [$full]) {
+            assert!(a.len() == dst.len() && b.len() == dst.len());
+            const H: usize = <$half as Lane>::LIMBS;
+            let mut a0 = [$half::ZERO; CHUNK];
+            let mut a1 = [$half::ZERO; CHUNK];
+            let mut b0 = [$half::ZERO; CHUNK];
+            let mut b1 = [$half::ZERO; CHUNK];
+            let mut c0 = [$half::ZERO; CHUNK];
+            let mut c1 = [$half::ZERO; CHUNK];
+
+            for start in (0..dst.len()).step_by(CHUNK) {
+                let n = core::cmp::min(CHUNK, dst.len() - start);
+                for i in 0..n {
+                    let (x, y) = (a[start + i].limbs(), b[start + i].limbs());
+                    a0[i].limbs_mut().copy_from_slice(&x[..H]);
+                    a1[i].limbs_mut().copy_from_slice(&x[H..]);
+                    b0[i].limbs_mut().copy_from_slice(&y[..H]);
+                    b1[i].limbs_mut().copy_from_slice(&y[H..]);
+                }
+                $half_widening(&a0[..n], &b0[..n], &mut dst[start..start + n]);
+                $half_wrapping(&a0[..n], &b1[..n], &mut c0[..n]);
+                $half_wrapping(&a1[..n], &b0[..n], &mut c1[..n]);
+
+                for i in 0..n {
+                    let d = dst[start + i].limbs_mut();
+                    limb::add_assign(&mut d[H..], c0[i].limbs());
+                    limb::add_assign(&mut d[H..], c1[i].limbs());
+                }
+            }
+        }
+    };
+}
+
+karatsuba!(
+    widening_mul_512,
+    wrapping_mul_512,
+    U256,
+    U512,
+    U1024,
+    lane::widening_mul_256,
+    lane::wrapping_mul_256
+);
+karatsuba!(
+    widening_mul_1024,
+    wrapping_mul_1024,
+    U512,
+    U1024,
+    U2048,
+    widening_mul_512,
+    wrapping_mul_512
+);
+
+/// Squares as `a0^2 + 2 * a0 * a1 * B + a1^2 * B^2`, with the middle term
+/// recovered as `a0^2 + a1^2 - (a0 - a1)^2`, so all three products are
+/// half-width squares.
+pub fn widening_sqr_512(a: &[U512], dst: &mut [U1024]) {
+    assert!(a.len() == dst.len());
+    let mut a0 = [U256::ZERO; CHUNK];
+    let mut a1 = [U256::ZERO; CHUNK];
+    let mut da = [U256::ZERO; CHUNK];
+    let mut z0 = [U512::ZERO; CHUNK];
+    let mut z2 = [U512::ZERO; CHUNK];
+    let mut zm = [U512::ZERO; CHUNK];
+
+    for start in (0..dst.len()).step_by(CHUNK) {
+        let n = core::cmp::min(CHUNK, dst.len() - start);
+        for i in 0..n {
+            let x = &a[start + i].0;
+            a0[i].0.copy_from_slice(&x[..4]);
+            a1[i].0.copy_from_slice(&x[4..]);
+            abs_diff(&x[..4], &x[4..], &mut da[i].0);
+        }
+        lane::widening_sqr_256(&a0[..n], &mut z0[..n]);
+        lane::widening_sqr_256(&a1[..n], &mut z2[..n]);
+        lane::widening_sqr_256(&da[..n], &mut zm[..n]);
+
+        for i in 0..n {
+            let d = &mut dst[start + i].0;
+            d[..8].copy_from_slice(&z0[i].0);
+            d[8..].copy_from_slice(&z2[i].0);
+            limb::add_assign(&mut d[4..], &z0[i].0);
+            limb::add_assign(&mut d[4..], &z2[i].0);
+            limb::sub_assign(&mut d[4..], &zm[i].0);
+        }
+    }
+}
+
+pub fn wrapping_sqr_512(a: &[U512], dst: &mut [U512]) {
+    assert!(a.len() == dst.len());
+    let mut a0 = [U256::ZERO; CHUNK];
+    let mut a1 = [U256::ZERO; CHUNK];
+    let mut c = [U256::ZERO; CHUNK];
+
+    for start in (0..dst.len()).step_by(CHUNK) {
+        let n = core::cmp::min(CHUNK, dst.len() - start);
+        for i in 0..n {
+            let x = &a[start + i].0;
+            a0[i].0.copy_from_slice(&x[..4]);
+            a1[i].0.copy_from_slice(&x[4..]);
+        }
+        lane::widening_sqr_256(&a0[..n], &mut dst[start..start + n]);
+        lane::wrapping_mul_256(&a0[..n], &a1[..n], &mut c[..n]);
+
+        for i in 0..n {
+            let d = &mut dst[start + i].0;
+            limb::add_assign(&mut d[4..], &c[i].0);
+            limb::add_assign(&mut d[4..], &c[i].0);
+        }
+    }
+}
+
+macro_rules! comba {
+    ($name:ident, $src:ident, $dst:ident) => {
+        pub fn $name(a: &[$src], b: &[$src], dst: &mut [$dst]) {
+            assert!(a.len() == dst.len() && b.len() == dst.len());
+            for i in 0..dst.len() {
+                limb::comba(a[i].limbs(), b[i].limbs(), dst[i].limbs_mut());
+            }
+        }
+    };
+}
+
+comba!(widening_mul_512_comba, U512, U1024);
+comba!(wrapping_mul_512_comba, U512, U512);
+comba!(widening_mul_1024_comba, U1024, U2048);
+comba!(wrapping_mul_1024_comba, U1024, U1024);
@@ -0,0 +1,234 @@
+//! The x86-64 backend: `adc`/`sbb` carry chains and `mul` rows in inline
+//! asm, most of them generated by build.rs.
+
+use core::arch::asm;
+
+// Unrolled add, sub and mul kernels, see `KERNELS` in build.rs
+include!(concat!(env!("OUT_DIR"), "/kernels_x86_64.rs"));
+
+pub fn msbc_256(a: *const u8, b: *const u8) -> bool {
+    let mut result: u64;
+    unsafe {
+        asm!(
+            "mov r8, [{1} + 0]",
+            "mov r9, [{1} + 8]",
+            "mov r10, [{1} + 16]",
+            "mov r11, [{1} + 24]",
+            "sub r8, [{2} + 0]",
+            "sbb r9, [{2} + 8]",
+            "sbb r10, [{2} + 16]",
+            "sbb r11, [{2} + 24]",
+            "sbb {0}, {0}",
+            out(reg) result,
+            in(reg) a as usize,
+            in(reg) b as usize,
+        );
+    }
+    result != 0
+}
+
+#[inline(always)]
+pub(crate) unsafe fn widening_mul_256_impl(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    for i in 0..len {
+        unsafe {
+            widening_mul_256_one(
+                a.wrapping_add(i * 32),
+                b.wrapping_add(i * 32),
+                dst.wrapping_add(i * 64),
+            );
+        }
+    }
+}
+
+#[inline(never)]
+pub fn widening_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    for i in 0..len {
+        unsafe {
+            widening_sqr_256_one(a.wrapping_add(i * 32), dst.wrapping_add(i * 64));
+        }
+    }
+}
+
+#[inline(never)]
+pub fn widening_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    for i in 0..len as isize {
+        unsafe {
+            widening_sqr_256_one(
+                a.wrapping_offset(i * a_stride),
+                dst.wrapping_offset(i * dst_stride),
+            );
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn widening_sqr_256_one(a: *const u8, dst: *mut u8) {
+    // 4 squares plus 6 cross products instead of 16: the squares go straight
+    // to dst, the cross products are summed in r9..r14, doubled with r15
+    // catching the top bit, then added onto the squares from memory.
+    asm!(
+        "mov rax, [rsi + 0]",
+        "mul rax",
+        "mov [rdi + 0], rax",
+        "mov [rdi + 8], rdx",
+        "mov rax, [rsi + 8]",
+        "mul rax",
+        "mov [rdi + 16], rax",
+        "mov [rdi + 24], rdx",
+        "mov rax, [rsi + 16]",
+        "mul rax",
+        "mov [rdi + 32], rax",
+        "mov [rdi + 40], rdx",
+        "mov rax, [rsi + 24]",
+        "mul rax",
+        "mov [rdi + 48], rax",
+        "mov [rdi + 56], rdx",
+        "",
+        "mov rax, [rsi + 0]",
+        "mul qword ptr [rsi + 8]",
+        "mov r9, rax",
+        "mov r10, rdx",
+        "mov rax, [rsi + 0]",
+        "mul qword ptr [rsi + 16]",
+        "add r10, rax",
+        "adc rdx, 0",
+        "mov r11, rdx",
+        "mov rax, [rsi + 0]",
+        "mul qword ptr [rsi + 24]",
+        "add r11, rax",
+        "adc rdx, 0",
+        "mov r12, rdx",
+        "",
+        "mov rax, [rsi + 8]",
+        "mul qword ptr [rsi + 16]",
+        "add r11, rax",
+        "adc rdx, 0",
+        "mov r13, rdx",
+        "mov rax, [rsi + 8]",
+        "mul qword ptr [rsi + 24]",
+        "add rax, r13",
+        "adc rdx, 0",
+        "add r12, rax",
+        "adc rdx, 0",
+        "mov r13, rdx",
+        "",
+        "mov rax, [rsi + 16]",
+        "mul qword ptr [rsi + 24]",
+        "add r13, rax",
+        "adc rdx, 0",
+        "mov r14, rdx",
+        "",
+        "xor r15, r15",
+        "add r9, r9",
+        "adc r10, r10",
+        "adc r11, r11",
+        "adc r12, r12",
+        "adc r13, r13",
+        "adc r14, r14",
+        "adc r15, 0",
+        "",
+        "add r9, [rdi + 8]",
+        "adc r10, [rdi + 16]",
+        "adc r11, [rdi + 24]",
+        "adc r12, [rdi + 32]",
+        "adc r13, [rdi + 40]",
+        "adc r14, [rdi + 48]",
+        "adc r15, [rdi + 56]",
+        "mov [rdi + 8], r9",
+        "mov [rdi + 16], r10",
+        "mov [rdi + 24], r11",
+        "mov [rdi + 32], r12",
+        "mov [rdi + 40], r13",
+        "mov [rdi + 48], r14",
+        "mov [rdi + 56], r15",
+        in("rsi") a as usize,
+        in("rdi") dst as usize,
+        lateout("r12") _,
+        lateout("r13") _,
+        lateout("r14") _,
+        lateout("r15") _,
+        clobber_abi("sysv64"),
+        clobber_abi("win64"),
+    );
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256(a: *const u8, dst: *mut u8, len: usize) {
+    for i in 0..len {
+        unsafe {
+            wrapping_sqr_256_one(a.wrapping_add(i * 32), dst.wrapping_add(i * 32));
+        }
+    }
+}
+
+#[inline(never)]
+pub fn wrapping_sqr_256_strided(
+    a: *const u8,
+    a_stride: isize,
+    dst: *mut u8,
+    dst_stride: isize,
+    len: usize,
+) {
+    for i in 0..len as isize {
+        unsafe {
+            wrapping_sqr_256_one(
+                a.wrapping_offset(i * a_stride),
+                dst.wrapping_offset(i * dst_stride),
+            );
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn wrapping_sqr_256_one(a: *const u8, dst: *mut u8) {
+    // Only 6 products reach the low 256 bits, two of them just their low
+    // halves, so everything stays in registers until the final stores.
+    asm!(
+        "mov rax, [rsi + 0]",
+        "mul qword ptr [rsi + 8]",
+        "mov r9, rax",
+        "mov r10, rdx",
+        "mov rax, [rsi + 0]",
+        "mul qword ptr [rsi + 16]",
+        "add r10, rax",
+        "adc rdx, 0",
+        "mov r11, rdx",
+        "mov rax, [rsi + 0]",
+        "imul rax, [rsi + 24]",
+        "add r11, rax",
+        "mov rax, [rsi + 8]",
+        "imul rax, [rsi + 16]",
+        "add r11, rax",
+        "",
+        "add r9, r9",
+        "adc r10, r10",
+        "adc r11, r11",
+        "",
+        "mov rax, [rsi + 8]",
+        "mul rax",
+        "mov r12, rax",
+        "mov r13, rdx",
+        "mov rax, [rsi + 0]",
+        "mul rax",
+        "add r9, rdx",
+        "adc r10, r12",
+        "adc r11, r13",
+        "",
+        "mov [rdi + 0], rax",
+        "mov [rdi + 8], r9",
+        "mov [rdi + 16], r10",
+        "mov [rdi + 24], r11",
+        in("rsi") a as usize,
+        in("rdi") dst as usize,
+        lateout("r12") _,
+        lateout("r13") _,
+        clobber_abi("sysv64"),
+        clobber_abi("win64"),
+    );
+}
@@ -0,0 +1,453 @@
+/*
+ * Calls the exported kernels from C and checks them against naive
+ * references on unsigned __int128. Build and run with `make -C tests/c`.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "fast_eint.h"
+
+typedef unsigned __int128 u128;
+
+static uint64_t state = 0x243f6a8885a308d3;
+
+static uint64_t next(void) {
+    /* xorshift64, good enough for test data */
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    return state;
+}
+
+static void fill(uint64_t *limbs, size_t n) {
+    for (size_t i = 0; i < n; i++) {
+        limbs[i] = next();
+    }
+}
+
+static void add_ref(const uint64_t *a, const uint64_t *b, uint64_t *dst, size_t n) {
+    u128 carry = 0;
+    for (size_t i = 0; i < n; i++) {
+        carry += (u128)a[i] + b[i];
+        dst[i] = (uint64_t)carry;
+        carry >>= 64;
+    }
+}
+
+static void sub_ref(const uint64_t *a, const uint64_t *b, uint64_t *dst, size_t n) {
+    uint64_t borrow = 0;
+    for (size_t i = 0; i < n; i++) {
+        u128 d = (u128)a[i] - b[i] - borrow;
+        dst[i] = (uint64_t)d;
+        borrow = (uint64_t)(d >> 64) & 1;
+    }
+}
+
+/* Schoolbook, keeping the low `out` limbs of the product */
+static void mul_ref(const uint64_t *a, const uint64_t *b, uint64_t *dst, size_t n, size_t out) {
+    memset(dst, 0, out * 8);
+    for (size_t i = 0; i < n; i++) {
+        uint64_t carry = 0;
+        for (size_t j = 0; j < n && i + j < out; j++) {
+            u128 t = (u128)a[i] * b[j] + dst[i + j] + carry;
+            dst[i + j] = (uint64_t)t;
+            carry = (uint64_t)(t >> 64);
+        }
+        if (i + n < out) {
+            dst[i + n] = carry;
+        }
+    }
+}
+
+static int failures = 0;
+
+static void check(const char *name, const uint64_t *got, const uint64_t *want, size_t n) {
+    if (memcmp(got, want, n * 8) != 0) {
+        printf("FAIL %s\n", name);
+        failures++;
+    }
+}
+
+#define LEN 16
+
+typedef void (*binary)(const uint8_t *, const uint8_t *, uint8_t *, size_t);
+typedef void (*binary_strided)(const uint8_t *, ptrdiff_t, const uint8_t *, ptrdiff_t,
+                               uint8_t *, ptrdiff_t, size_t);
+
+enum op { ADD, SUB, MUL };
+
+static void test_binary(const char *name, binary f, binary_strided g, enum op op, size_t n,
+                        size_t out) {
+    uint64_t a[LEN * 8], b[LEN * 8], dst[LEN * 16], want[16];
+    fill(a, LEN * n);
+    fill(b, LEN * n);
+
+    f((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        const uint64_t *x = a + i * n, *y = b + i * n;
+        switch (op) {
+        case ADD: add_ref(x, y, want, n); break;
+        case SUB: sub_ref(x, y, want, n); break;
+        case MUL: mul_ref(x, y, want, n, out); break;
+        }
+        check(name, dst + i * out, want, out);
+    }
+
+    /* Reversed source order through negative strides */
+    memset(dst, 0, sizeof(dst));
+    ptrdiff_t stride = (ptrdiff_t)(n * 8);
+    g((const uint8_t *)(a + (LEN - 1) * n), -stride, (const uint8_t *)(b + (LEN - 1) * n),
+      -stride, (uint8_t *)dst, (ptrdiff_t)(out * 8), LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        const uint64_t *x = a + (LEN - 1 - i) * n, *y = b + (LEN - 1 - i) * n;
+        switch (op) {
+        case ADD: add_ref(x, y, want, n); break;
+        case SUB: sub_ref(x, y, want, n); break;
+        case MUL: mul_ref(x, y, want, n, out); break;
+        }
+        check(name, dst + i * out, want, out);
+    }
+}
+
+static void test_sqr(void) {
+    uint64_t a[LEN * 4], wide[LEN * 8], narrow[LEN * 4], want[8];
+    fill(a, LEN * 4);
+    fast_eint_widening_sqr_256((const uint8_t *)a, (uint8_t *)wide, LEN);
+    fast_eint_wrapping_sqr_256_strided((const uint8_t *)a, 32, (uint8_t *)narrow, 32, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        mul_ref(a + i * 4, a + i * 4, want, 4, 8);
+        check("widening_sqr_256", wide + i * 8, want, 8);
+        check("wrapping_sqr_256_strided", narrow + i * 4, want, 4);
+    }
+}
+
+static void test_shift(void) {
+    uint64_t src[LEN * 8], dst[LEN * 4], want[4];
+    fill(src, LEN * 8);
+    for (uint32_t shift = 0; shift < 512; shift += 37) {
+        fast_eint_narrowing_right_shift_512((const uint8_t *)src, (uint8_t *)dst, shift, LEN);
+        for (size_t i = 0; i < LEN; i++) {
+            const uint64_t *x = src + i * 8;
+            for (size_t j = 0; j < 4; j++) {
+                size_t limb = j + shift / 64, bits = shift % 64;
+                uint64_t lo = limb < 8 ? x[limb] : 0, hi = limb + 1 < 8 ? x[limb + 1] : 0;
+                want[j] = bits ? (lo >> bits) | (hi << (64 - bits)) : lo;
+            }
+            check("narrowing_right_shift_512", dst + i * 4, want, 4);
+        }
+    }
+}
+
+static void test_msbc(void) {
+    uint64_t a[4], b[4];
+    for (int i = 0; i < 64; i++) {
+        fill(a, 4);
+        memcpy(b, a, sizeof(b));
+        b[i % 4] ^= next() & 3;
+        bool want = false;
+        for (int j = 3; j >= 0; j--) {
+            if (a[j] != b[j]) {
+                want = a[j] < b[j];
+                break;
+            }
+        }
+        if (fast_eint_msbc_256((const uint8_t *)a, (const uint8_t *)b) != want) {
+            printf("FAIL msbc_256\n");
+            failures++;
+        }
+    }
+}
+
+/* The lane-slice exports need 8-byte aligned buffers, which uint64_t gives */
+typedef void (*lanes_binary)(const uint8_t *, const uint8_t *, uint8_t *, size_t);
+typedef void (*lanes_unary)(const uint8_t *, uint8_t *, size_t);
+
+static void test_wide(const char *name, lanes_binary f, lanes_unary sqr, size_t n,
+                      size_t out) {
+    uint64_t a[LEN * 16], b[LEN * 16], dst[LEN * 32], want[32];
+    fill(a, LEN * n);
+    fill(b, LEN * n);
+    f((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        mul_ref(a + i * n, b + i * n, want, n, out);
+        check(name, dst + i * out, want, out);
+    }
+    if (sqr) {
+        sqr((const uint8_t *)a, (uint8_t *)dst, LEN);
+        for (size_t i = 0; i < LEN; i++) {
+            mul_ref(a + i * n, a + i * n, want, n, out);
+            check(name, dst + i * out, want, out);
+        }
+    }
+}
+
+static void test_ct(void) {
+    uint64_t a[LEN * 8], b[LEN * 4], mask[LEN], dst[LEN * 4], want[4];
+    fill(a, LEN * 8);
+    memcpy(b, a, LEN * 32);
+    for (size_t i = 0; i < LEN; i += 2) {
+        b[i * 4 + i % 4] ^= 1;
+    }
+
+    fast_eint_ct_eq_256((const uint8_t *)a, (const uint8_t *)b, mask, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        uint64_t expected = memcmp(a + i * 4, b + i * 4, 32) == 0 ? ~0ull : 0;
+        check("ct_eq_256", mask + i, &expected, 1);
+    }
+    fast_eint_ct_lt_256((const uint8_t *)a, (const uint8_t *)b, mask, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        bool lt = false;
+        for (int j = 3; j >= 0; j--) {
+            if (a[i * 4 + j] != b[i * 4 + j]) {
+                lt = a[i * 4 + j] < b[i * 4 + j];
+                break;
+            }
+        }
+        uint64_t expected = lt ? ~0ull : 0;
+        check("ct_lt_256", mask + i, &expected, 1);
+    }
+
+    for (size_t i = 0; i < LEN; i++) {
+        mask[i] = fast_eint_ct_mask_nonzero(i & 1);
+    }
+    fast_eint_ct_select_256(mask, (const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        check("ct_select_256", dst + i * 4, (i & 1 ? a : b) + i * 4, 4);
+    }
+    memcpy(dst, a, LEN * 32);
+    fast_eint_ct_swap_256(mask, (uint8_t *)dst, (uint8_t *)b, LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        /* Odd lanes swapped, so both now hold a's value there */
+        check("ct_swap_256", dst + i * 4, (i & 1 ? b : a) + i * 4, 4);
+        if (i & 1) {
+            check("ct_swap_256", b + i * 4, a + i * 4, 4);
+        }
+    }
+
+    for (uint32_t shift = 0; shift < 512; shift += 37) {
+        fast_eint_ct_wrapping_shl_256((const uint8_t *)a, shift, (uint8_t *)dst, LEN);
+        for (size_t i = 0; i < LEN; i++) {
+            for (size_t j = 0; j < 4; j++) {
+                uint32_t s = shift % 256;
+                size_t k = s / 64, bits = s % 64;
+                uint64_t hi = j >= k ? a[i * 4 + j - k] : 0;
+                uint64_t lo = j >= k + 1 ? a[i * 4 + j - k - 1] : 0;
+                want[j] = bits ? (hi << bits) | (lo >> (64 - bits)) : hi;
+            }
+            check("ct_wrapping_shl_256", dst + i * 4, want, 4);
+        }
+        fast_eint_ct_wrapping_shr_256((const uint8_t *)a, shift, (uint8_t *)dst, LEN);
+        for (size_t i = 0; i < LEN; i++) {
+            for (size_t j = 0; j < 4; j++) {
+                uint32_t s = shift % 256;
+                size_t k = j + s / 64, bits = s % 64;
+                uint64_t lo = k < 4 ? a[i * 4 + k] : 0, hi = k + 1 < 4 ? a[i * 4 + k + 1] : 0;
+                want[j] = bits ? (lo >> bits) | (hi << (64 - bits)) : lo;
+            }
+            check("ct_wrapping_shr_256", dst + i * 4, want, 4);
+        }
+        fast_eint_ct_narrowing_right_shift_512((const uint8_t *)a, (uint8_t *)dst, shift, LEN / 2);
+        uint64_t expected[LEN * 2];
+        fast_eint_narrowing_right_shift_512((const uint8_t *)a, (uint8_t *)expected, shift,
+                                            LEN / 2);
+        check("ct_narrowing_right_shift_512", dst, expected, LEN * 2);
+    }
+}
+
+#if defined(__x86_64__) || defined(_M_X64)
+static void test_simd(void) {
+    /* An unsupported backend aborts, so only run what this CPU has */
+    __builtin_cpu_init();
+    const bool supported[] = {
+        [FAST_EINT_BACKEND_SCALAR] = true,
+        [FAST_EINT_BACKEND_AVX2] = __builtin_cpu_supports("avx2"),
+        [FAST_EINT_BACKEND_AVX512] = __builtin_cpu_supports("avx512f"),
+        [FAST_EINT_BACKEND_IFMA] =
+            __builtin_cpu_supports("avx512f") && __builtin_cpu_supports("avx512ifma"),
+    };
+    uint64_t a[LEN * 8], b[LEN * 8], dst[LEN * 8], want[LEN * 8];
+    fill(a, LEN * 8);
+    fill(b, LEN * 8);
+
+    for (uint32_t backend = 0; backend < sizeof(supported) / sizeof(supported[0]); backend++) {
+        if (!supported[backend]) {
+            continue;
+        }
+
+        fast_eint_widening_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+        fast_eint_simd_widening_mul_256_with(backend, (const uint8_t *)a, (const uint8_t *)b,
+                                             (uint8_t *)dst, LEN);
+        check("simd_widening_mul_256_with", dst, want, LEN * 8);
+
+        fast_eint_wrapping_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+        fast_eint_simd_wrapping_mul_256_with(backend, (const uint8_t *)a, (const uint8_t *)b,
+                                             (uint8_t *)dst, LEN);
+        check("simd_wrapping_mul_256_with", dst, want, LEN * 4);
+
+        fast_eint_wrapping_add_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+        fast_eint_simd_wrapping_add_256_with(backend, (const uint8_t *)a, (const uint8_t *)b,
+                                             (uint8_t *)dst, LEN);
+        check("simd_wrapping_add_256_with", dst, want, LEN * 4);
+
+        fast_eint_wrapping_sub_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+        fast_eint_simd_wrapping_sub_256_with(backend, (const uint8_t *)a, (const uint8_t *)b,
+                                             (uint8_t *)dst, LEN);
+        check("simd_wrapping_sub_256_with", dst, want, LEN * 4);
+
+        fast_eint_wrapping_add_512((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+        fast_eint_simd_wrapping_add_512_with(backend, (const uint8_t *)a, (const uint8_t *)b,
+                                             (uint8_t *)dst, LEN);
+        check("simd_wrapping_add_512_with", dst, want, LEN * 8);
+    }
+
+    fast_eint_widening_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+    fast_eint_simd_widening_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    check("simd_widening_mul_256", dst, want, LEN * 8);
+    fast_eint_wrapping_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+    fast_eint_simd_wrapping_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    check("simd_wrapping_mul_256", dst, want, LEN * 4);
+    fast_eint_wrapping_add_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+    fast_eint_simd_wrapping_add_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    check("simd_wrapping_add_256", dst, want, LEN * 4);
+    fast_eint_wrapping_sub_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+    fast_eint_simd_wrapping_sub_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    check("simd_wrapping_sub_256", dst, want, LEN * 4);
+    fast_eint_wrapping_add_512((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)want, LEN);
+    fast_eint_simd_wrapping_add_512((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)dst, LEN);
+    check("simd_wrapping_add_512", dst, want, LEN * 8);
+}
+#endif
+
+/*
+ * The modular contexts against each other and against identities, on the
+ * secp256k1 field prime. The Rust tests check them against eint.
+ */
+static void test_modular(void) {
+    static const uint64_t p[4] = {0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff,
+                                  0xffffffffffffffff};
+    static const uint64_t one[4] = {1, 0, 0, 0}, two[4] = {2, 0, 0, 0};
+    uint64_t a[LEN * 4], b[LEN * 4], wide[LEN * 8], e[LEN * 4];
+    uint64_t x[LEN * 4], y[LEN * 4], z[LEN * 4];
+
+    uint64_t zero[4] = {0};
+    if (fast_eint_montgomery256_new((const uint8_t *)zero) != NULL ||
+        fast_eint_modulus256_new((const uint8_t *)zero) != NULL) {
+        printf("FAIL modular zero modulus\n");
+        failures++;
+    }
+
+    fast_eint_modulus256 *modulus = fast_eint_modulus256_new((const uint8_t *)p);
+    fast_eint_montgomery256 *mont = fast_eint_montgomery256_new((const uint8_t *)p);
+    fast_eint_barrett256 *barrett = fast_eint_barrett256_new((const uint8_t *)p);
+
+    fill(a, LEN * 4);
+    fill(b, LEN * 4);
+    fast_eint_modulus256_reduce(modulus, (const uint8_t *)a, (uint8_t *)x, LEN);
+    memcpy(a, x, sizeof(a));
+    fast_eint_modulus256_reduce(modulus, (const uint8_t *)b, (uint8_t *)x, LEN);
+    memcpy(b, x, sizeof(b));
+
+    /* (a + b) - b == a */
+    fast_eint_modulus256_addmod(modulus, (const uint8_t *)a, (const uint8_t *)b, (uint8_t *)x,
+                                LEN);
+    fast_eint_modulus256_submod(modulus, (const uint8_t *)x, (const uint8_t *)b, (uint8_t *)y,
+                                LEN);
+    check("modulus256_addmod", y, a, LEN * 4);
+
+    /* mulmod, Barrett on the full product and Montgomery all agree */
+    fast_eint_modulus256_mulmod(modulus, (const uint8_t *)a, (const uint8_t *)b, (uint8_t *)x,
+                                LEN);
+    fast_eint_widening_mul_256((const uint8_t *)a, (const uint8_t *)b, (uint8_t *)wide, LEN);
+    fast_eint_barrett256_reduce_512(barrett, (const uint8_t *)wide, (uint8_t *)y, LEN);
+    check("barrett256_reduce_512", y, x, LEN * 4);
+
+    uint64_t am[LEN * 4], bm[LEN * 4];
+    fast_eint_montgomery256_to_mont(mont, (const uint8_t *)a, (uint8_t *)am, LEN);
+    fast_eint_montgomery256_to_mont(mont, (const uint8_t *)b, (uint8_t *)bm, LEN);
+    fast_eint_montgomery256_mont_mul(mont, (const uint8_t *)am, (const uint8_t *)bm,
+                                     (uint8_t *)z, LEN);
+    fast_eint_montgomery256_from_mont(mont, (const uint8_t *)z, (uint8_t *)y, LEN);
+    check("montgomery256_mont_mul", y, x, LEN * 4);
+
+    /* a^2 three ways */
+    fast_eint_modulus256_mulmod(modulus, (const uint8_t *)a, (const uint8_t *)a, (uint8_t *)x,
+                                LEN);
+    fast_eint_montgomery256_mont_sqr(mont, (const uint8_t *)am, (uint8_t *)z, LEN);
+    fast_eint_montgomery256_from_mont(mont, (const uint8_t *)z, (uint8_t *)y, LEN);
+    check("montgomery256_mont_sqr", y, x, LEN * 4);
+    fast_eint_montgomery256_powmod(mont, (const uint8_t *)a, (const uint8_t *)two, (uint8_t *)y,
+                                   LEN);
+    check("montgomery256_powmod", y, x, LEN * 4);
+    for (size_t i = 0; i < LEN; i++) {
+        memcpy(e + i * 4, two, 32);
+    }
+    fast_eint_montgomery256_powmod_each(mont, (const uint8_t *)a, (const uint8_t *)e,
+                                        (uint8_t *)y, LEN);
+    check("montgomery256_powmod_each", y, x, LEN * 4);
+
+    /* a * a^-1 == 1, from both inversions */
+    if (!fast_eint_montgomery256_invert(mont, (const uint8_t *)a, (uint8_t *)x, LEN)) {
+        printf("FAIL montgomery256_invert\n");
+        failures++;
+    }
+    fast_eint_montgomery256_invert_prime(mont, (const uint8_t *)a, (uint8_t *)y, LEN);
+    check("montgomery256_invert_prime", y, x, LEN * 4);
+    fast_eint_modulus256_mulmod(modulus, (const uint8_t *)a, (const uint8_t *)x, (uint8_t *)y,
+                                LEN);
+    for (size_t i = 0; i < LEN; i++) {
+        check("montgomery256_invert", y + i * 4, one, 4);
+    }
+
+    fast_eint_modulus256_free(modulus);
+    fast_eint_montgomery256_free(mont);
+    fast_eint_barrett256_free(barrett);
+    fast_eint_montgomery256_free(NULL);
+}
+
+int main(void) {
+    test_binary("wrapping_add_256", fast_eint_wrapping_add_256,
+                fast_eint_wrapping_add_256_strided, ADD, 4, 4);
+    test_binary("wrapping_add_512", fast_eint_wrapping_add_512,
+                fast_eint_wrapping_add_512_strided, ADD, 8, 8);
+    test_binary("wrapping_sub_256", fast_eint_wrapping_sub_256,
+                fast_eint_wrapping_sub_256_strided, SUB, 4, 4);
+    test_binary("wrapping_sub_512", fast_eint_wrapping_sub_512,
+                fast_eint_wrapping_sub_512_strided, SUB, 8, 8);
+    test_binary("wrapping_mul_256", fast_eint_wrapping_mul_256,
+                fast_eint_wrapping_mul_256_strided, MUL, 4, 4);
+    test_binary("wrapping_mul_512", fast_eint_wrapping_mul_512,
+                fast_eint_wrapping_mul_512_strided, MUL, 8, 8);
+    test_binary("widening_mul_256", fast_eint_widening_mul_256,
+                fast_eint_widening_mul_256_strided, MUL, 4, 8);
+    test_binary("widening_mul_512", fast_eint_widening_mul_512,
+                fast_eint_widening_mul_512_strided, MUL, 8, 16);
+    test_sqr();
+    test_shift();
+    test_msbc();
+    test_wide("wide_widening_mul_512", fast_eint_wide_widening_mul_512,
+              fast_eint_wide_widening_sqr_512, 8, 16);
+    test_wide("wide_wrapping_mul_512", fast_eint_wide_wrapping_mul_512,
+              fast_eint_wide_wrapping_sqr_512, 8, 8);
+    test_wide("wide_widening_mul_1024", fast_eint_wide_widening_mul_1024, NULL, 16, 32);
+    test_wide("wide_wrapping_mul_1024", fast_eint_wide_wrapping_mul_1024, NULL, 16, 16);
+    test_wide("wide_widening_mul_512_comba", fast_eint_wide_widening_mul_512_comba, NULL, 8, 16);
+    test_wide("wide_wrapping_mul_512_comba", fast_eint_wide_wrapping_mul_512_comba, NULL, 8, 8);
+    test_wide("wide_widening_mul_1024_comba", fast_eint_wide_widening_mul_1024_comba, NULL, 16,
+              32);
+    test_wide("wide_wrapping_mul_1024_comba", fast_eint_wide_wrapping_mul_1024_comba, NULL, 16,
+              16);
+    test_ct();
+#if defined(__x86_64__) || defined(_M_X64)
+    test_simd();
+#endif
+    test_modular();
+
+    if (failures) {
+        printf("%d failures\n", failures);
+        return EXIT_FAILURE;
+    }
+    printf("all C ABI tests passed\n");
+    return EXIT_SUCCESS;
+}
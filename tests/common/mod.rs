@@ -0,0 +1,492 @@
+//! Differential testing of every backend against eint.
+//!
+//! Each kernel is described once in [`OPS`], by its element sizes, how to
+//! call it through [`EintBackend`] and how to compute one element with eint.
+//! The checks run every descriptor on every backend in [`Dispatch::ALL`],
+//! on random elements and on [`edge_cases`], so a new kernel only needs an
+//! entry here to be covered. [`supported_backends`] lists the simd backends
+//! for the tests and benches that call the `_with` dispatchers directly.
+
+// Not every test crate uses every helper
+#![allow(dead_code)]
+
+use eint::{Eint, E1024, E256, E512};
+use fast_eint::backend::{Dispatch, EintBackend};
+use fast_eint::lane::{as_bytes, as_bytes_mut};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+pub enum Kind {
+    /// `(a, b, dst, len)`
+    Binary {
+        kernel: fn(&Dispatch, *const u8, *const u8, *mut u8, usize),
+        reference: fn(&[u8], &[u8], &mut [u8]),
+    },
+    /// `(a, dst, len)`
+    Unary {
+        kernel: fn(&Dispatch, *const u8, *mut u8, usize),
+        reference: fn(&[u8], &mut [u8]),
+    },
+    /// `(src, dst, shift, len)`
+    Shift {
+        kernel: fn(&Dispatch, *const u8, *mut u8, u32, usize),
+        reference: fn(&[u8], u32, &mut [u8]),
+    },
+    /// `(a, b) -> bool`, one element only
+    Compare {
+        kernel: fn(&Dispatch, *const u8, *const u8) -> bool,
+        reference: fn(&[u8], &[u8]) -> bool,
+    },
+}
+
+/// One kernel, with `width` bytes per operand and `out` bytes per result.
+pub struct Op {
+    pub name: &'static str,
+    pub width: usize,
+    pub out: usize,
+    pub kind: Kind,
+}
+
+/// Amounts around every limb boundary and the masking at 512.
+pub const SHIFTS: &[u32] = &[
+    0, 1, 63, 64, 65, 127, 128, 191, 255, 256, 319, 383, 447, 448, 511, 512, 575, 1023,
+];
+
+/// Calls a strided kernel on the elements in reverse order, through
+/// negative strides from the last element of every buffer. That leaves each
+/// result where the contiguous kernel puts it.
+macro_rules! reversed {
+    (binary $name:ident, $width:expr, $out:expr) => {
+        |backend: &Dispatch, a: *const u8, b: *const u8, dst: *mut u8, len: usize| {
+            let last = len.saturating_sub(1);
+            backend.$name(
+                a.wrapping_add(last * $width),
+                -$width,
+                b.wrapping_add(last * $width),
+                -$width,
+                dst.wrapping_add(last * $out),
+                -$out,
+                len,
+            )
+        }
+    };
+    (unary $name:ident, $width:expr, $out:expr) => {
+        |backend: &Dispatch, a: *const u8, dst: *mut u8, len: usize| {
+            let last = len.saturating_sub(1);
+            backend.$name(
+                a.wrapping_add(last * $width),
+                -$width,
+                dst.wrapping_add(last * $out),
+                -$out,
+                len,
+            )
+        }
+    };
+    (shift $name:ident, $width:expr, $out:expr) => {
+        |backend: &Dispatch, src: *const u8, dst: *mut u8, shift: u32, len: usize| {
+            let last = len.saturating_sub(1);
+            backend.$name(
+                src.wrapping_add(last * $width),
+                -$width,
+                dst.wrapping_add(last * $out),
+                -$out,
+                shift,
+                len,
+            )
+        }
+    };
+}
+
+macro_rules! binary {
+    ($name:ident, $ty:ident, $width:expr, $op:ident) => {
+        binary!($name, <Dispatch as EintBackend>::$name, $ty, $width, $op)
+    };
+    ($name:ident, $kernel:expr, $ty:ident, $width:expr, $op:ident) => {
+        Op {
+            name: stringify!($name),
+            width: $width,
+            out: $width,
+            kind: Kind::Binary {
+                kernel: $kernel,
+                reference: |a, b, dst| $ty::get(a).$op($ty::get(b)).put(dst),
+            },
+        }
+    };
+}
+
+macro_rules! widening {
+    ($name:ident, $ty:ident, $width:expr) => {
+        widening!($name, <Dispatch as EintBackend>::$name, $ty, $width)
+    };
+    ($name:ident, $kernel:expr, $ty:ident, $width:expr) => {
+        Op {
+            name: stringify!($name),
+            width: $width,
+            out: 2 * $width,
+            kind: Kind::Binary {
+                kernel: $kernel,
+                reference: |a, b, dst| {
+                    let (lo, hi) = $ty::get(a).widening_mul_u($ty::get(b));
+                    lo.put(&mut dst[..$width]);
+                    hi.put(&mut dst[$width..]);
+                },
+            },
+        }
+    };
+}
+
+macro_rules! wrapping_sqr {
+    ($name:ident, $kernel:expr, $ty:ident, $width:expr) => {
+        Op {
+            name: stringify!($name),
+            width: $width,
+            out: $width,
+            kind: Kind::Unary {
+                kernel: $kernel,
+                reference: |a, dst| {
+                    let a = $ty::get(a);
+                    a.wrapping_mul(a).put(dst)
+                },
+            },
+        }
+    };
+}
+
+macro_rules! widening_sqr {
+    ($name:ident, $kernel:expr, $ty:ident, $width:expr) => {
+        Op {
+            name: stringify!($name),
+            width: $width,
+            out: 2 * $width,
+            kind: Kind::Unary {
+                kernel: $kernel,
+                reference: |a, dst| {
+                    let a = $ty::get(a);
+                    let (lo, hi) = a.widening_mul_u(a);
+                    lo.put(&mut dst[..$width]);
+                    hi.put(&mut dst[$width..]);
+                },
+            },
+        }
+    };
+}
+
+macro_rules! narrowing_right_shift_512 {
+    ($name:ident, $kernel:expr) => {
+        Op {
+            name: stringify!($name),
+            width: 64,
+            out: 32,
+            kind: Kind::Shift {
+                kernel: $kernel,
+                reference: |src, shift, dst| E512::get(src).wrapping_shr(shift).put_lo(dst),
+            },
+        }
+    };
+}
+
+/// The byte-offset `widening_mul_256` on one buffer holding the results,
+/// then `a`, then `b`.
+fn widening_mul_256_mem(backend: &Dispatch, a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    let mut mem = vec![0u64; 16 * len];
+    let mem = as_bytes_mut(&mut mem);
+    unsafe {
+        mem[64 * len..96 * len].copy_from_slice(std::slice::from_raw_parts(a, 32 * len));
+        mem[96 * len..].copy_from_slice(std::slice::from_raw_parts(b, 32 * len));
+    }
+    backend.widening_mul_256_mem(mem, 0, 64 * len, 96 * len, len);
+    unsafe { std::ptr::copy_nonoverlapping(mem.as_ptr(), dst, 64 * len) };
+}
+
+pub const OPS: &[Op] = &[
+    binary!(wrapping_add_256, E256, 32, wrapping_add),
+    binary!(wrapping_add_512, E512, 64, wrapping_add),
+    binary!(wrapping_sub_256, E256, 32, wrapping_sub),
+    binary!(wrapping_sub_512, E512, 64, wrapping_sub),
+    binary!(wrapping_mul_256, E256, 32, wrapping_mul),
+    binary!(wrapping_mul_512, E512, 64, wrapping_mul),
+    widening!(widening_mul_256, E256, 32),
+    widening!(widening_mul_512, E512, 64),
+    wrapping_sqr!(
+        wrapping_sqr_256,
+        <Dispatch as EintBackend>::wrapping_sqr_256,
+        E256,
+        32
+    ),
+    widening_sqr!(
+        widening_sqr_256,
+        <Dispatch as EintBackend>::widening_sqr_256,
+        E256,
+        32
+    ),
+    narrowing_right_shift_512!(
+        narrowing_right_shift_512,
+        <Dispatch as EintBackend>::narrowing_right_shift_512
+    ),
+    Op {
+        name: "msbc_256",
+        width: 32,
+        out: 0,
+        kind: Kind::Compare {
+            kernel: <Dispatch as EintBackend>::msbc_256,
+            reference: |a, b| E256::get(a).overflowing_sub_u(E256::get(b)).1,
+        },
+    },
+    widening!(widening_mul_256_mem, widening_mul_256_mem, E256, 32),
+    binary!(
+        wrapping_add_256_strided,
+        reversed!(binary wrapping_add_256_strided, 32, 32),
+        E256,
+        32,
+        wrapping_add
+    ),
+    binary!(
+        wrapping_add_512_strided,
+        reversed!(binary wrapping_add_512_strided, 64, 64),
+        E512,
+        64,
+        wrapping_add
+    ),
+    binary!(
+        wrapping_sub_256_strided,
+        reversed!(binary wrapping_sub_256_strided, 32, 32),
+        E256,
+        32,
+        wrapping_sub
+    ),
+    binary!(
+        wrapping_sub_512_strided,
+        reversed!(binary wrapping_sub_512_strided, 64, 64),
+        E512,
+        64,
+        wrapping_sub
+    ),
+    binary!(
+        wrapping_mul_256_strided,
+        reversed!(binary wrapping_mul_256_strided, 32, 32),
+        E256,
+        32,
+        wrapping_mul
+    ),
+    binary!(
+        wrapping_mul_512_strided,
+        reversed!(binary wrapping_mul_512_strided, 64, 64),
+        E512,
+        64,
+        wrapping_mul
+    ),
+    widening!(
+        widening_mul_256_strided,
+        reversed!(binary widening_mul_256_strided, 32, 64),
+        E256,
+        32
+    ),
+    widening!(
+        widening_mul_512_strided,
+        reversed!(binary widening_mul_512_strided, 64, 128),
+        E512,
+        64
+    ),
+    wrapping_sqr!(
+        wrapping_sqr_256_strided,
+        reversed!(unary wrapping_sqr_256_strided, 32, 32),
+        E256,
+        32
+    ),
+    widening_sqr!(
+        widening_sqr_256_strided,
+        reversed!(unary widening_sqr_256_strided, 32, 64),
+        E256,
+        32
+    ),
+    narrowing_right_shift_512!(
+        narrowing_right_shift_512_strided,
+        reversed!(shift narrowing_right_shift_512_strided, 64, 32)
+    ),
+    binary!(wrapping_mul_1024, E1024, 128, wrapping_mul),
+    widening!(widening_mul_1024, E1024, 128),
+    wrapping_sqr!(
+        wrapping_sqr_512,
+        <Dispatch as EintBackend>::wrapping_sqr_512,
+        E512,
+        64
+    ),
+    widening_sqr!(
+        widening_sqr_512,
+        <Dispatch as EintBackend>::widening_sqr_512,
+        E512,
+        64
+    ),
+];
+
+/// Values that stress the carry and borrow chains of a `width`-byte
+/// operand: zero, one, all ones, the top bit, and for every limb boundary
+/// `k` the values `2^(64k) - 1` and `2^(64k)`, which carry or borrow across
+/// the low `k` limbs when combined with one, and a single full limb `k`.
+pub fn edge_cases(width: usize) -> Vec<Vec<u8>> {
+    let mut cases = vec![vec![0u8; width], vec![0xffu8; width]];
+
+    let mut one = vec![0u8; width];
+    one[0] = 1;
+    cases.push(one);
+
+    let mut top = vec![0u8; width];
+    top[width - 1] = 0x80;
+    cases.push(top);
+
+    let mut below_top = vec![0xffu8; width];
+    below_top[width - 1] = 0x7f;
+    cases.push(below_top);
+
+    for k in 1..width / 8 {
+        let mut low = vec![0u8; width];
+        low[..8 * k].fill(0xff);
+        cases.push(low);
+
+        let mut boundary = vec![0u8; width];
+        boundary[8 * k] = 1;
+        cases.push(boundary);
+    }
+    for k in 0..width / 8 {
+        let mut limb = vec![0u8; width];
+        limb[8 * k..8 * k + 8].fill(0xff);
+        cases.push(limb);
+    }
+    cases
+}
+
+/// Runs `op` on `backend` over the packed elements `buf_a` and `buf_b` and
+/// compares every result with eint. `buf_b` is unused by unary ops and
+/// shifts, which take `shift` instead.
+pub fn check(op: &Op, backend: Dispatch, buf_a: &[u8], buf_b: &[u8], shift: u32) {
+    let len = buf_a.len() / op.width;
+    let context = |i: usize| format!("{} on {} backend, element {}", op.name, backend.name(), i);
+
+    // The wide lanes and the C shift need 8-byte aligned buffers
+    let (buf_a, buf_b) = (&aligned(buf_a), &aligned(buf_b));
+    let (buf_a, buf_b) = (as_bytes(buf_a), as_bytes(buf_b));
+    let mut buf_c = vec![0xaaaa_aaaa_aaaa_aaaau64; op.out * len / 8];
+    let buf_c = as_bytes_mut(&mut buf_c);
+    let mut buf_expected = vec![0u8; op.out * len];
+    let a = |i: usize| &buf_a[i * op.width..i * op.width + op.width];
+    let b = |i: usize| &buf_b[i * op.width..i * op.width + op.width];
+
+    match op.kind {
+        Kind::Binary { kernel, reference } => {
+            for i in 0..len {
+                reference(
+                    a(i),
+                    b(i),
+                    &mut buf_expected[i * op.out..i * op.out + op.out],
+                );
+            }
+            kernel(
+                &backend,
+                buf_a.as_ptr(),
+                buf_b.as_ptr(),
+                buf_c.as_mut_ptr(),
+                len,
+            );
+        }
+        Kind::Unary { kernel, reference } => {
+            for i in 0..len {
+                reference(a(i), &mut buf_expected[i * op.out..i * op.out + op.out]);
+            }
+            kernel(&backend, buf_a.as_ptr(), buf_c.as_mut_ptr(), len);
+        }
+        Kind::Shift { kernel, reference } => {
+            for i in 0..len {
+                reference(
+                    a(i),
+                    shift,
+                    &mut buf_expected[i * op.out..i * op.out + op.out],
+                );
+            }
+            kernel(&backend, buf_a.as_ptr(), buf_c.as_mut_ptr(), shift, len);
+        }
+        Kind::Compare { kernel, reference } => {
+            for i in 0..len {
+                let actual = kernel(&backend, a(i).as_ptr(), b(i).as_ptr());
+                assert_eq!(actual, reference(a(i), b(i)), "{}", context(i));
+            }
+            return;
+        }
+    }
+
+    for i in 0..len {
+        assert_eq!(
+            buf_c[i * op.out..i * op.out + op.out],
+            buf_expected[i * op.out..i * op.out + op.out],
+            "{}",
+            context(i)
+        );
+    }
+}
+
+fn aligned(bytes: &[u8]) -> Vec<u64> {
+    let mut limbs = vec![0u64; bytes.len().div_ceil(8)];
+    as_bytes_mut(&mut limbs)[..bytes.len()].copy_from_slice(bytes);
+    limbs
+}
+
+/// Every pair of edge cases as one batch, and for shifts every edge case
+/// at every amount in [`SHIFTS`].
+pub fn check_edges(op: &Op, backend: Dispatch) {
+    let cases = edge_cases(op.width);
+    let mut buf_a = vec![];
+    let mut buf_b = vec![];
+    for x in &cases {
+        for y in &cases {
+            buf_a.extend_from_slice(x);
+            buf_b.extend_from_slice(y);
+        }
+    }
+    match op.kind {
+        Kind::Shift { .. } => {
+            let buf_a = cases.concat();
+            for &shift in SHIFTS {
+                check(op, backend, &buf_a, &[], shift);
+            }
+        }
+        _ => check(op, backend, &buf_a, &buf_b, 0),
+    }
+}
+
+/// `len` random elements from `seed`.
+pub fn check_random(op: &Op, backend: Dispatch, seed: u64, len: usize, shift: u32) {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf_a = vec![0u8; op.width * len];
+    let mut buf_b = vec![0u8; op.width * len];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    check(op, backend, &buf_a, &buf_b, shift);
+}
+
+/// Edge cases and one random batch of every op on `backend`.
+pub fn check_backend(backend: Dispatch, seed: u64, len: usize, shift: u32) {
+    for op in OPS {
+        check_edges(op, backend);
+        check_random(op, backend, seed, len, shift);
+    }
+}
+
+/// Every simd backend this CPU can run. Each `_with` dispatcher accepts all
+/// of them and falls back where it has no kernel for one.
+#[cfg(target_arch = "x86_64")]
+pub fn supported_backends() -> Vec<fast_eint::simd::Backend> {
+    use fast_eint::simd::Backend;
+
+    let mut backends = vec![Backend::Scalar];
+    if is_x86_feature_detected!("avx2") {
+        backends.push(Backend::Avx2);
+    }
+    if is_x86_feature_detected!("avx512f") {
+        backends.push(Backend::Avx512);
+        if is_x86_feature_detected!("avx512ifma") {
+            backends.push(Backend::Ifma);
+        }
+    }
+    backends
+}
@@ -18,7 +18,7 @@ fn test_single_add_512() {
     let a = E512::get(&buf_a);
     let b = E512::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_add(b.clone());
+        let c = a.wrapping_add(b);
 
         let mut buf = vec![0u8; 64];
         c.put(&mut buf[..]);
@@ -50,7 +50,7 @@ fn test_single_add_512_same_a() {
     let a = E512::get(&buf_a);
     let b = E512::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_add(b.clone());
+        let c = a.wrapping_add(b);
 
         let mut buf = vec![0u8; 64];
         c.put(&mut buf[..]);
@@ -75,7 +75,7 @@ fn test_single_add_512_same_b() {
     let a = E512::get(&buf_a);
     let b = E512::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_add(b.clone());
+        let c = a.wrapping_add(b);
 
         let mut buf = vec![0u8; 64];
         c.put(&mut buf[..]);
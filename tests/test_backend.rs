@@ -0,0 +1,37 @@
+mod common;
+
+use fast_eint::backend::{Asm, Dispatch, EintBackend, Portable};
+
+#[test]
+fn test_dispatch_names() {
+    for &backend in Dispatch::ALL {
+        assert_eq!(Dispatch::from_name(backend.name()), Some(backend));
+    }
+    assert_eq!(Dispatch::from_name("none"), None);
+    assert!(Dispatch::ALL.contains(&Dispatch::default()));
+    assert_eq!(Dispatch::Asm.name(), Asm.name());
+    assert_eq!(Dispatch::Portable.name(), Portable.name());
+}
+
+#[test]
+fn test_suite_asm() {
+    common::check_backend(Dispatch::Asm, 100, 8, 111);
+}
+
+#[test]
+fn test_suite_portable() {
+    common::check_backend(Dispatch::Portable, 100, 8, 111);
+}
+
+#[cfg(feature = "c-reference")]
+#[test]
+fn test_suite_c() {
+    common::check_backend(Dispatch::C, 100, 8, 111);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_suite_simd() {
+    // Past the thresholds, so the vector kernels run where supported
+    common::check_backend(Dispatch::Simd, 100, 35, 111);
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "c-reference")]
+
+use fast_eint::{
+    msbc_256, msbc_256_c, msbc_512_c, widening_mul_256_c, widening_mul_256_strided,
+    widening_mul_512, widening_mul_512_c, widening_sqr_256, widening_sqr_256_c, wrapping_add_256,
+    wrapping_add_256_c, wrapping_add_512, wrapping_add_512_c, wrapping_mul_256, wrapping_mul_256_c,
+    wrapping_mul_512, wrapping_mul_512_c, wrapping_sqr_256, wrapping_sqr_256_c, wrapping_sub_256,
+    wrapping_sub_256_c, wrapping_sub_512, wrapping_sub_512_c,
+};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+type Binary = fn(*const u8, *const u8, *mut u8, usize);
+
+// The asm kernels are checked against eint elsewhere, so the C ones only
+// need to agree with them. `offset` misaligns every buffer.
+fn check_binary(
+    seed: u64,
+    offset: usize,
+    len: usize,
+    width: usize,
+    out: usize,
+    asm: Binary,
+    c: Binary,
+) {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf_a = vec![0u8; offset + width * len];
+    let mut buf_b = vec![0u8; offset + width * len];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let mut expected = vec![0u8; offset + out * len];
+    let mut buf_c = vec![0u8; offset + out * len];
+
+    asm(
+        buf_a[offset..].as_ptr(),
+        buf_b[offset..].as_ptr(),
+        expected[offset..].as_mut_ptr(),
+        len,
+    );
+    c(
+        buf_a[offset..].as_ptr(),
+        buf_b[offset..].as_ptr(),
+        buf_c[offset..].as_mut_ptr(),
+        len,
+    );
+    assert_eq!(buf_c, expected);
+}
+
+// The contiguous widening_mul_256 takes offsets into one buffer instead
+fn widening_mul_256_asm(a: *const u8, b: *const u8, dst: *mut u8, len: usize) {
+    widening_mul_256_strided(a, 32, b, 32, dst, 64, len);
+}
+
+fn squares(seed: u64, offset: usize, len: usize) {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf_a = vec![0u8; offset + 32 * len];
+    rng.fill_bytes(&mut buf_a);
+    let a = buf_a[offset..].as_ptr();
+
+    let mut expected = vec![0u8; 64 * len];
+    let mut buf_c = vec![0u8; 64 * len];
+    widening_sqr_256(a, expected.as_mut_ptr(), len);
+    widening_sqr_256_c(a, buf_c.as_mut_ptr(), len);
+    assert_eq!(buf_c, expected);
+    wrapping_sqr_256(a, expected.as_mut_ptr(), len);
+    wrapping_sqr_256_c(a, buf_c.as_mut_ptr(), len);
+    assert_eq!(buf_c[..32 * len], expected[..32 * len]);
+}
+
+#[test]
+fn test_c_reference_carries() {
+    let ones = [0xffu8; 64];
+    let mut one = [0u8; 64];
+    one[0] = 1;
+    let mut dst = [0xaau8; 128];
+
+    wrapping_add_512_c(ones.as_ptr(), one.as_ptr(), dst.as_mut_ptr(), 1);
+    assert_eq!(dst[..64], [0u8; 64]);
+    wrapping_sub_256_c(one.as_ptr(), ones.as_ptr(), dst.as_mut_ptr(), 1);
+    // 1 - (2^256 - 1) wraps to 2
+    assert_eq!(dst[0], 2);
+    assert_eq!(dst[1..32], [0u8; 31]);
+    widening_mul_512_c(ones.as_ptr(), ones.as_ptr(), dst.as_mut_ptr(), 1);
+    // (2^512 - 1)^2 = 2^1024 - 2^513 + 1
+    assert_eq!(dst[0], 1);
+    assert_eq!(dst[1..64], [0u8; 63]);
+    assert_eq!(dst[64], 0xfe);
+    assert_eq!(dst[65..], [0xffu8; 63]);
+
+    assert!(msbc_256_c(one.as_ptr(), ones.as_ptr()));
+    assert!(!msbc_512_c(ones.as_ptr(), one.as_ptr()));
+    assert!(!msbc_512_c(one.as_ptr(), one.as_ptr()));
+}
+
+proptest! {
+    #[test]
+    fn random_batch_c_reference(seed in 0u64.., offset in 0usize..8, len in 0usize..24) {
+        check_binary(seed, offset, len, 32, 32, wrapping_add_256, wrapping_add_256_c);
+        check_binary(seed, offset, len, 64, 64, wrapping_add_512, wrapping_add_512_c);
+        check_binary(seed, offset, len, 32, 32, wrapping_sub_256, wrapping_sub_256_c);
+        check_binary(seed, offset, len, 64, 64, wrapping_sub_512, wrapping_sub_512_c);
+        check_binary(seed, offset, len, 32, 32, wrapping_mul_256, wrapping_mul_256_c);
+        check_binary(seed, offset, len, 64, 64, wrapping_mul_512, wrapping_mul_512_c);
+        check_binary(seed, offset, len, 32, 64, widening_mul_256_asm, widening_mul_256_c);
+        check_binary(seed, offset, len, 64, 128, widening_mul_512, widening_mul_512_c);
+        squares(seed, offset, len);
+    }
+
+    #[test]
+    fn random_msbc_c_reference(a in prop::array::uniform4(0u64..4), b in prop::array::uniform4(0u64..4)) {
+        let (a, b): (Vec<u8>, Vec<u8>) = (
+            a.iter().flat_map(|l| l.to_le_bytes()).collect(),
+            b.iter().flat_map(|l| l.to_le_bytes()).collect(),
+        );
+        prop_assert_eq!(msbc_256_c(a.as_ptr(), b.as_ptr()), msbc_256(a.as_ptr(), b.as_ptr()));
+    }
+}
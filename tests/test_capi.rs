@@ -0,0 +1,124 @@
+#![cfg(feature = "capi")]
+
+use eint::{Eint, E256, E512};
+use fast_eint::capi::{
+    fast_eint_ct_lt_256, fast_eint_modulus256_free, fast_eint_modulus256_mulmod,
+    fast_eint_modulus256_new, fast_eint_msbc_256, fast_eint_narrowing_right_shift_512_strided,
+    fast_eint_wide_widening_mul_512, fast_eint_widening_mul_256, fast_eint_wrapping_add_512,
+    fast_eint_wrapping_sqr_256_strided,
+};
+use fast_eint::lane::{as_bytes, as_bytes_mut, U1024, U256, U512};
+use fast_eint::modular::Modulus256;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+/// The checked-in header is what C users include; regenerate it with
+/// `cp target/*/build/fast-eint-*/out/fast_eint.h include/` after changing
+/// the export list in build.rs.
+#[test]
+fn test_header_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/fast_eint.h"));
+    assert_eq!(generated, include_str!("../include/fast_eint.h"));
+}
+
+#[test]
+fn test_batch_capi_8() {
+    let mut rng = ChaCha20Rng::seed_from_u64(41);
+    let mut buf_a = vec![0u8; 64 * 8];
+    let mut buf_b = vec![0u8; 64 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+
+    let mut add = vec![0u8; 64 * 8];
+    let mut mul = vec![0u8; 64 * 8];
+    let mut sqr = vec![0u8; 32 * 8];
+    let mut nrs = vec![0u8; 32 * 8];
+    fast_eint_wrapping_add_512(buf_a.as_ptr(), buf_b.as_ptr(), add.as_mut_ptr(), 8);
+    fast_eint_widening_mul_256(buf_a.as_ptr(), buf_b.as_ptr(), mul.as_mut_ptr(), 8);
+    // Every other 256-bit half of buf_a
+    fast_eint_wrapping_sqr_256_strided(buf_a.as_ptr(), 64, sqr.as_mut_ptr(), 32, 8);
+    fast_eint_narrowing_right_shift_512_strided(buf_a.as_ptr(), 64, nrs.as_mut_ptr(), 32, 100, 8);
+
+    let mut expected = [0u8; 64];
+    for i in 0..8 {
+        let (a, b) = (&buf_a[i * 64..i * 64 + 64], &buf_b[i * 64..i * 64 + 64]);
+        E512::get(a).wrapping_add(E512::get(b)).put(&mut expected);
+        assert_eq!(&add[i * 64..i * 64 + 64], &expected[..]);
+
+        // Contiguous 256-bit operands are packed 32 bytes apart
+        let x = E256::get(&buf_a[i * 32..i * 32 + 32]);
+        let y = E256::get(&buf_b[i * 32..i * 32 + 32]);
+        let (lo, hi) = x.widening_mul_u(y);
+        lo.put(&mut expected[..32]);
+        hi.put(&mut expected[32..]);
+        assert_eq!(&mul[i * 64..i * 64 + 64], &expected[..]);
+
+        let (a256, b256) = (E256::get(&a[..32]), E256::get(&b[..32]));
+        a256.wrapping_mul(a256).put(&mut expected[..32]);
+        assert_eq!(&sqr[i * 32..i * 32 + 32], &expected[..32]);
+
+        E512::get(a).wrapping_shr(100).put_lo(&mut expected[..32]);
+        assert_eq!(&nrs[i * 32..i * 32 + 32], &expected[..32]);
+
+        assert_eq!(fast_eint_msbc_256(a.as_ptr(), b.as_ptr()), a256.lt(&b256));
+    }
+}
+
+/// The lane-slice exports on aligned buffers, against the Rust API.
+#[test]
+fn test_lane_capi_8() {
+    let mut rng = ChaCha20Rng::seed_from_u64(41);
+    let mut a = [U512::ZERO; 8];
+    let mut b = [U512::ZERO; 8];
+    rng.fill_bytes(as_bytes_mut(&mut a));
+    rng.fill_bytes(as_bytes_mut(&mut b));
+
+    let mut wide = [U1024::ZERO; 8];
+    let mut expected = [U1024::ZERO; 8];
+    fast_eint_wide_widening_mul_512(
+        as_bytes(&a).as_ptr(),
+        as_bytes(&b).as_ptr(),
+        as_bytes_mut(&mut wide).as_mut_ptr(),
+        8,
+    );
+    fast_eint::wide::widening_mul_512(&a, &b, &mut expected);
+    assert_eq!(wide, expected);
+
+    let x: Vec<U256> = a
+        .iter()
+        .map(|a| U256([a.0[0], a.0[1], a.0[2], a.0[3]]))
+        .collect();
+    let y: Vec<U256> = b
+        .iter()
+        .map(|b| U256([b.0[0], b.0[1], b.0[2], b.0[3]]))
+        .collect();
+    let mut masks = [0u64; 8];
+    fast_eint_ct_lt_256(
+        as_bytes(&x).as_ptr(),
+        as_bytes(&y).as_ptr(),
+        masks.as_mut_ptr(),
+        8,
+    );
+    for i in 0..8 {
+        let lt = E256::get(&x[i].to_le_bytes()).lt(&E256::get(&y[i].to_le_bytes()));
+        assert_eq!(masks[i], if lt { u64::MAX } else { 0 });
+    }
+
+    let m = U256([u64::MAX - 188, u64::MAX, u64::MAX, u64::MAX]);
+    let ctx = fast_eint_modulus256_new(m.to_le_bytes().as_ptr());
+    assert!(!ctx.is_null());
+    let mut product = [U256::ZERO; 8];
+    let mut expected = [U256::ZERO; 8];
+    fast_eint_modulus256_mulmod(
+        ctx,
+        as_bytes(&x).as_ptr(),
+        as_bytes(&y).as_ptr(),
+        as_bytes_mut(&mut product).as_mut_ptr(),
+        8,
+    );
+    fast_eint_modulus256_free(ctx);
+    Modulus256::new(m).unwrap().mulmod(&x, &y, &mut expected);
+    assert_eq!(product, expected);
+}
@@ -0,0 +1,261 @@
+use eint::{Eint, E256, E512};
+use fast_eint::ct::{
+    eq_256, lt_256, narrowing_right_shift_512, select_256, swap_256, wrapping_shl_256,
+    wrapping_shr_256,
+};
+use fast_eint::lane::{as_bytes_mut, U256, U512};
+use fast_eint::modular::{Modulus256, Montgomery256};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+fn e256(a: &U256) -> E256 {
+    E256::get(&a.to_le_bytes())
+}
+
+fn u256(a: E256) -> U256 {
+    let mut bytes = [0u8; 32];
+    a.put(&mut bytes);
+    U256::from_le_bytes(&bytes)
+}
+
+#[test]
+fn test_masks_and_select() {
+    let a = [U256([1, 0, 0, 5]), U256([u64::MAX; 4]), U256([3, 0, 0, 0])];
+    let b = [U256([2, 0, 0, 5]), U256([u64::MAX; 4]), U256([0, 0, 0, 1])];
+
+    let mut eq = [0u64; 3];
+    let mut lt = [0u64; 3];
+    eq_256(&a, &b, &mut eq);
+    lt_256(&a, &b, &mut lt);
+    assert_eq!(eq, [0, u64::MAX, 0]);
+    assert_eq!(lt, [u64::MAX, 0, u64::MAX]);
+
+    let mut dst = [U256::ZERO; 3];
+    select_256(&lt, &a, &b, &mut dst);
+    assert_eq!(dst, [a[0], b[1], a[2]]);
+
+    let (mut x, mut y) = (a, b);
+    swap_256(&lt, &mut x, &mut y);
+    assert_eq!(x, [b[0], a[1], b[2]]);
+    assert_eq!(y, [a[0], b[1], a[2]]);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_ct_shift(
+        a in prop::array::uniform16(prop::array::uniform8(0u64..)),
+        shift in 0u32..1024u32,
+    ) {
+        let wide: Vec<U512> = a.iter().map(|l| U512(*l)).collect();
+        let narrow: Vec<U256> = a.iter().map(|l| U256([l[0], l[1], l[2], l[3]])).collect();
+
+        let mut nrs = vec![U256::ZERO; 16];
+        let mut shl = vec![U256::ZERO; 16];
+        let mut shr = vec![U256::ZERO; 16];
+        narrowing_right_shift_512(&wide, &mut nrs, shift);
+        wrapping_shl_256(&narrow, shift, &mut shl);
+        wrapping_shr_256(&narrow, shift, &mut shr);
+
+        let mut expected = [0u8; 32];
+        for i in 0..16 {
+            E512::get(&wide[i].to_le_bytes()).wrapping_shr(shift).put_lo(&mut expected);
+            prop_assert_eq!(nrs[i], U256::from_le_bytes(&expected));
+            prop_assert_eq!(shl[i], u256(e256(&narrow[i]).wrapping_shl(shift)));
+            prop_assert_eq!(shr[i], u256(e256(&narrow[i]).wrapping_shr(shift)));
+        }
+    }
+
+    #[test]
+    fn random_batch_16_ct_masks(
+        a in prop::array::uniform16(prop::array::uniform4(0u64..4)),
+        b in prop::array::uniform16(prop::array::uniform4(0u64..4)),
+    ) {
+        let a: Vec<U256> = a.iter().map(|l| U256(*l)).collect();
+        let b: Vec<U256> = b.iter().map(|l| U256(*l)).collect();
+
+        let mut eq = vec![0u64; 16];
+        let mut lt = vec![0u64; 16];
+        eq_256(&a, &b, &mut eq);
+        lt_256(&a, &b, &mut lt);
+
+        for i in 0..16 {
+            prop_assert_eq!(eq[i], (a[i] == b[i]) as u64 * u64::MAX);
+            prop_assert_eq!(lt[i], e256(&a[i]).lt(&e256(&b[i])) as u64 * u64::MAX);
+        }
+    }
+}
+
+// dudect-style leakage detection (Reparaz, Balasch, Verbauwhede, "Dude, is
+// my code constant time?"). Every measurement runs the kernel on either a
+// fixed input or a random one, picked at random, and Welch's t-test compares
+// the two timing distributions. |t| above 10 is a clear leak.
+//
+// These are statistical and want a quiet machine and an optimized build:
+//
+//     cargo test --release --test test_ct -- --ignored --nocapture
+
+const MEASUREMENTS: usize = 200_000;
+const T_THRESHOLD: f64 = 10.0;
+
+#[derive(Default)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var0 = self.m2[0] / (self.n[0] - 1.0);
+        let var1 = self.m2[1] / (self.n[1] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var0 / self.n[0] + var1 / self.n[1]).sqrt()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn cycles() -> u64 {
+    unsafe { std::arch::x86_64::_rdtsc() }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cycles() -> u64 {
+    use std::time::Instant;
+    thread_local!(static START: Instant = Instant::now());
+    START.with(|s| s.elapsed().as_nanos() as u64)
+}
+
+/// Returns the largest |t| over the raw data and a few upper percentile
+/// crops, which is how dudect guards against interrupts and other outliers
+/// drowning the signal. All inputs are generated before timing starts, so
+/// the two classes differ only in their values.
+fn dudect<S, I, F>(seed: u64, mut input: I, mut run: F) -> f64
+where
+    I: FnMut(&mut ChaCha20Rng, usize) -> S,
+    F: FnMut(&S),
+{
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let inputs: Vec<(usize, S)> = (0..MEASUREMENTS)
+        .map(|_| {
+            let class = (rng.next_u32() & 1) as usize;
+            (class, input(&mut rng, class))
+        })
+        .collect();
+    let mut samples = Vec::with_capacity(MEASUREMENTS);
+    for (class, state) in inputs.iter() {
+        let start = cycles();
+        run(state);
+        samples.push((*class, cycles().wrapping_sub(start) as f64));
+    }
+
+    let mut sorted: Vec<f64> = samples.iter().map(|s| s.1).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut worst: f64 = 0.0;
+    for percentile in [1.0, 0.99, 0.9, 0.5] {
+        let cutoff = sorted[((sorted.len() - 1) as f64 * percentile) as usize];
+        let mut welch = Welch::default();
+        for (class, x) in samples.iter().filter(|s| s.1 <= cutoff) {
+            welch.push(*class, *x);
+        }
+        worst = worst.max(welch.t().abs());
+    }
+    worst
+}
+
+/// The BN254 base field prime
+const BN254_P: U256 = U256([
+    0x3c20_8c16_d87c_fd47,
+    0x9781_6a91_6871_ca8d,
+    0xb850_45b6_8181_585d,
+    0x3064_4e72_e131_a029,
+]);
+
+// Class 0 is all zeros, class 1 uniformly random
+fn lanes_of_class(rng: &mut ChaCha20Rng, class: usize) -> [U256; 4] {
+    let mut lanes = [U256::ZERO; 4];
+    if class == 1 {
+        rng.fill_bytes(as_bytes_mut(&mut lanes));
+    }
+    lanes
+}
+
+#[test]
+#[ignore]
+fn dudect_ct_narrowing_right_shift_512() {
+    let mut dst = [U256::ZERO; 4];
+    let t = dudect(
+        1,
+        |rng, class| {
+            let mut src = [U512::ZERO; 4];
+            rng.fill_bytes(as_bytes_mut(&mut src));
+            (src, if class == 0 { 0 } else { rng.next_u32() })
+        },
+        |(src, shift)| narrowing_right_shift_512(src, &mut dst, *shift),
+    );
+    println!("ct::narrowing_right_shift_512 |t| = {:.2}", t);
+    assert!(t < T_THRESHOLD);
+}
+
+#[test]
+#[ignore]
+fn dudect_montgomery_mul() {
+    let mont = Montgomery256::new(BN254_P).unwrap();
+    let mut dst = [U256::ZERO; 4];
+    let t = dudect(2, lanes_of_class, |a| mont.mont_mul(a, a, &mut dst));
+    println!("Montgomery256::mont_mul |t| = {:.2}", t);
+    assert!(t < T_THRESHOLD);
+}
+
+// One lane each, the 512 GCD steps and 336 multiplications are slow enough
+
+#[test]
+#[ignore]
+fn dudect_montgomery_invert() {
+    let mont = Montgomery256::new(BN254_P).unwrap();
+    let mut dst = [U256::ZERO; 1];
+    let t = dudect(
+        4,
+        |rng, class| [lanes_of_class(rng, class)[0]],
+        |a| {
+            mont.invert(a, &mut dst);
+        },
+    );
+    println!("Montgomery256::invert |t| = {:.2}", t);
+    assert!(t < T_THRESHOLD);
+}
+
+#[test]
+#[ignore]
+fn dudect_montgomery_powmod() {
+    let mont = Montgomery256::new(BN254_P).unwrap();
+    let base = [U256([3, 0, 0, 0])];
+    let mut dst = [U256::ZERO; 1];
+    let t = dudect(
+        5,
+        |rng, class| lanes_of_class(rng, class)[0],
+        |e| mont.powmod(&base, e, &mut dst),
+    );
+    println!("Montgomery256::powmod |t| = {:.2}", t);
+    assert!(t < T_THRESHOLD);
+}
+
+/// Makes sure the harness can actually see something: long division takes
+/// a shortcut for inputs below the modulus, which zero always is.
+#[test]
+#[ignore]
+fn dudect_detects_leaky_reduce() {
+    let modulus = Modulus256::new(U256([u64::MAX, u64::MAX, 1, 0])).unwrap();
+    let mut dst = [U256::ZERO; 4];
+    let t = dudect(3, lanes_of_class, |a| modulus.reduce(a, &mut dst));
+    println!("Modulus256::reduce |t| = {:.2}", t);
+    assert!(t > T_THRESHOLD);
+}
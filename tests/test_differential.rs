@@ -0,0 +1,29 @@
+mod common;
+
+use common::{check_edges, check_random, OPS};
+use fast_eint::backend::Dispatch;
+use proptest::prelude::*;
+
+#[test]
+fn test_edge_cases() {
+    for op in OPS {
+        for &backend in Dispatch::ALL {
+            check_edges(op, backend);
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_differential(
+        seed in any::<u64>(),
+        len in 0usize..40,
+        shift in 0u32..1024u32,
+    ) {
+        for op in OPS {
+            for &backend in Dispatch::ALL {
+                check_random(op, backend, seed, len, shift);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "eint")]
+
+use eint::{Eint, E1024, E2048, E256, E512};
+use fast_eint::eint::{
+    as_e256, as_u256, msbc_256, narrowing_right_shift_512, widening_mul_1024, widening_mul_256,
+    widening_mul_512, wrapping_add_256, wrapping_add_512, wrapping_mul_1024, wrapping_mul_256,
+    wrapping_mul_512, wrapping_sub_256,
+};
+use fast_eint::lane::U256;
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+fn random_e256(rng: &mut ChaCha20Rng, len: usize) -> Vec<E256> {
+    let mut buf = vec![0u8; 32];
+    (0..len)
+        .map(|_| {
+            rng.fill_bytes(&mut buf);
+            E256::get(&buf)
+        })
+        .collect()
+}
+
+#[test]
+fn test_eint_lane_views() {
+    let mut rng = ChaCha20Rng::seed_from_u64(100);
+    let a = random_e256(&mut rng, 4);
+
+    let lanes = as_u256(&a);
+    for i in 0..4 {
+        assert_eq!(lanes[i], U256::from(a[i]));
+        assert_eq!(E256::from(lanes[i]), a[i]);
+    }
+    assert_eq!(as_e256(lanes), &a[..]);
+}
+
+#[test]
+fn test_batch_eint_mul_8_256() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+    let a = random_e256(&mut rng, 8);
+    let b = random_e256(&mut rng, 8);
+
+    let mut wide = vec![E512::default(); 8];
+    widening_mul_256(&a, &b, &mut wide);
+    let mut narrow = vec![E256::default(); 8];
+    wrapping_mul_256(&a, &b, &mut narrow);
+
+    for i in 0..8 {
+        let (lo, hi) = a[i].widening_mul_u(b[i]);
+        assert_eq!(E512::from(lo) | (E512::from(hi) << 256), wide[i]);
+        assert_eq!(lo, narrow[i]);
+    }
+
+    let mut shifted = vec![E256::default(); 8];
+    narrowing_right_shift_512(&wide, &mut shifted, 256);
+    for i in 0..8 {
+        assert_eq!(a[i].widening_mul_u(b[i]).1, shifted[i]);
+    }
+}
+
+#[test]
+fn test_batch_eint_mul_wide() {
+    let mut rng = ChaCha20Rng::seed_from_u64(124);
+    let a = random_e256(&mut rng, 64);
+    let b = random_e256(&mut rng, 64);
+    let a512: Vec<E512> = a
+        .chunks(2)
+        .map(|c| E512::from(c[0]) | (E512::from(c[1]) << 256))
+        .collect();
+    let b512: Vec<E512> = b
+        .chunks(2)
+        .map(|c| E512::from(c[0]) | (E512::from(c[1]) << 256))
+        .collect();
+    let a1024: Vec<E1024> = a512
+        .chunks(2)
+        .map(|c| E1024::from(c[0]) | (E1024::from(c[1]) << 512))
+        .collect();
+    let b1024: Vec<E1024> = b512
+        .chunks(2)
+        .map(|c| E1024::from(c[0]) | (E1024::from(c[1]) << 512))
+        .collect();
+
+    let mut wide = vec![E1024::default(); 32];
+    let mut narrow = vec![E512::default(); 32];
+    widening_mul_512(&a512, &b512, &mut wide);
+    wrapping_mul_512(&a512, &b512, &mut narrow);
+    for i in 0..32 {
+        let (lo, hi) = a512[i].widening_mul_u(b512[i]);
+        assert_eq!(E1024::from(lo) | (E1024::from(hi) << 512), wide[i]);
+        assert_eq!(lo, narrow[i]);
+    }
+
+    let mut wide = vec![E2048::default(); 16];
+    let mut narrow = vec![E1024::default(); 16];
+    widening_mul_1024(&a1024, &b1024, &mut wide);
+    wrapping_mul_1024(&a1024, &b1024, &mut narrow);
+    for i in 0..16 {
+        let (lo, hi) = a1024[i].widening_mul_u(b1024[i]);
+        assert_eq!(E2048::from(lo) | (E2048::from(hi) << 1024), wide[i]);
+        assert_eq!(lo, narrow[i]);
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_eint_add_sub(
+        a in prop::array::uniform16(prop::array::uniform4(0u64..)),
+        b in prop::array::uniform16(prop::array::uniform4(0u64..)),
+    ) {
+        let a: Vec<E256> = a.iter().map(|l| E256(*l)).collect();
+        let b: Vec<E256> = b.iter().map(|l| E256(*l)).collect();
+
+        let mut sum = vec![E256::default(); 16];
+        wrapping_add_256(&a, &b, &mut sum);
+        let mut diff = vec![E256::default(); 16];
+        wrapping_sub_256(&a, &b, &mut diff);
+
+        for i in 0..16 {
+            assert_eq!(a[i].wrapping_add(b[i]), sum[i]);
+            assert_eq!(a[i].wrapping_sub(b[i]), diff[i]);
+            assert_eq!(a[i].overflowing_sub_u(b[i]).1, msbc_256(&a[i], &b[i]));
+        }
+    }
+
+    #[test]
+    fn random_batch_16_eint_add_512(
+        a in prop::array::uniform16(prop::array::uniform8(0u64..)),
+        b in prop::array::uniform16(prop::array::uniform8(0u64..)),
+    ) {
+        let a: Vec<E512> = a.iter().map(|l| E512(*l)).collect();
+        let b: Vec<E512> = b.iter().map(|l| E512(*l)).collect();
+
+        let mut sum = vec![E512::default(); 16];
+        wrapping_add_512(&a, &b, &mut sum);
+
+        for i in 0..16 {
+            assert_eq!(a[i].wrapping_add(b[i]), sum[i]);
+        }
+    }
+}
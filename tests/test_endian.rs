@@ -0,0 +1,132 @@
+use eint::{Eint, E256, E512};
+use fast_eint::endian::{
+    msbc_256, widening_mul_256, wrapping_add_256, wrapping_add_512, wrapping_mul_256,
+    wrapping_sub_256, BigEndian, LittleEndian,
+};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+// Big-endian lanes are the little-endian encoding with the bytes reversed
+fn reversed(buf: &[u8], width: usize) -> Vec<u8> {
+    buf.chunks(width)
+        .flat_map(|c| c.iter().rev().copied())
+        .collect()
+}
+
+#[test]
+fn test_single_be_sbc_256() {
+    let mut a = [0u8; 32];
+    let mut b = [0u8; 32];
+    a[0] = 1;
+    b[31] = 0xff;
+
+    // a = 2^248, b = 255 when read as big-endian
+    assert!(!msbc_256::<BigEndian>(a.as_ptr(), b.as_ptr()));
+    assert!(msbc_256::<BigEndian>(b.as_ptr(), a.as_ptr()));
+    assert!(msbc_256::<LittleEndian>(a.as_ptr(), b.as_ptr()));
+}
+
+#[test]
+fn test_batch_be_mul_37_256() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+
+    let mut buf_a = vec![0u8; 32 * 37];
+    let mut buf_b = vec![0u8; 32 * 37];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let le_a = reversed(&buf_a, 32);
+    let le_b = reversed(&buf_b, 32);
+
+    let mut expected_wide = vec![0u8; 64 * 37];
+    let mut expected_narrow = vec![0u8; 32 * 37];
+    for i in 0..37 {
+        let a = E256::get(&le_a[i * 32..i * 32 + 32]);
+        let b = E256::get(&le_b[i * 32..i * 32 + 32]);
+
+        let (lo, hi) = a.widening_mul_u(b);
+
+        lo.put(&mut expected_wide[i * 64..i * 64 + 32]);
+        hi.put(&mut expected_wide[i * 64 + 32..i * 64 + 64]);
+        lo.put(&mut expected_narrow[i * 32..i * 32 + 32]);
+    }
+
+    let mut buf_wide = vec![0u8; 64 * 37];
+    let mut buf_narrow = vec![0u8; 32 * 37];
+    widening_mul_256::<BigEndian>(buf_a.as_ptr(), buf_b.as_ptr(), buf_wide.as_mut_ptr(), 37);
+    wrapping_mul_256::<BigEndian>(buf_a.as_ptr(), buf_b.as_ptr(), buf_narrow.as_mut_ptr(), 37);
+
+    assert_eq!(reversed(&buf_wide, 64), expected_wide);
+    assert_eq!(reversed(&buf_narrow, 32), expected_narrow);
+}
+
+#[test]
+fn test_batch_be_add_8_256_same_a() {
+    let mut rng = ChaCha20Rng::seed_from_u64(100);
+
+    let mut buf_a = vec![0u8; 32 * 8];
+    let mut buf_b = vec![0u8; 32 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let le_a = reversed(&buf_a, 32);
+    let le_b = reversed(&buf_b, 32);
+
+    let mut expected = vec![0u8; 32 * 8];
+    for i in 0..8 {
+        let a = E256::get(&le_a[i * 32..i * 32 + 32]);
+        let b = E256::get(&le_b[i * 32..i * 32 + 32]);
+
+        a.wrapping_add(b).put(&mut expected[i * 32..i * 32 + 32]);
+    }
+
+    wrapping_add_256::<BigEndian>(buf_a.as_ptr(), buf_b.as_ptr(), buf_a.as_mut_ptr(), 8);
+
+    assert_eq!(reversed(&buf_a, 32), expected);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_20_be_add_sub(
+        a in prop::collection::vec(0u8.., 64 * 20),
+        b in prop::collection::vec(0u8.., 64 * 20),
+    ) {
+        let le_a256 = reversed(&a, 32);
+        let le_b256 = reversed(&b, 32);
+        let le_a512 = reversed(&a, 64);
+        let le_b512 = reversed(&b, 64);
+
+        let mut expected_add = vec![0u8; 32 * 40];
+        let mut expected_sub = vec![0u8; 32 * 40];
+        let mut expected_add512 = vec![0u8; 64 * 20];
+        for i in 0..40 {
+            let x = E256::get(&le_a256[i * 32..i * 32 + 32]);
+            let y = E256::get(&le_b256[i * 32..i * 32 + 32]);
+
+            x.wrapping_add(y).put(&mut expected_add[i * 32..i * 32 + 32]);
+            x.wrapping_sub(y).put(&mut expected_sub[i * 32..i * 32 + 32]);
+            prop_assert_eq!(
+                x.overflowing_sub_u(y).1,
+                msbc_256::<BigEndian>(a[i * 32..].as_ptr(), b[i * 32..].as_ptr())
+            );
+        }
+        for i in 0..20 {
+            let x = E512::get(&le_a512[i * 64..i * 64 + 64]);
+            let y = E512::get(&le_b512[i * 64..i * 64 + 64]);
+
+            x.wrapping_add(y).put(&mut expected_add512[i * 64..i * 64 + 64]);
+        }
+
+        let mut buf_add = vec![0u8; 32 * 40];
+        let mut buf_sub = vec![0u8; 32 * 40];
+        let mut buf_add512 = vec![0u8; 64 * 20];
+        wrapping_add_256::<BigEndian>(a.as_ptr(), b.as_ptr(), buf_add.as_mut_ptr(), 40);
+        wrapping_sub_256::<BigEndian>(a.as_ptr(), b.as_ptr(), buf_sub.as_mut_ptr(), 40);
+        wrapping_add_512::<BigEndian>(a.as_ptr(), b.as_ptr(), buf_add512.as_mut_ptr(), 20);
+
+        prop_assert_eq!(reversed(&buf_add, 32), expected_add);
+        prop_assert_eq!(reversed(&buf_sub, 32), expected_sub);
+        prop_assert_eq!(reversed(&buf_add512, 64), expected_add512);
+    }
+}
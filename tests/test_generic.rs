@@ -0,0 +1,119 @@
+use eint::{Eint, E1024};
+use fast_eint::generic::{
+    cmp, cmp_dyn, widening_mul, widening_mul_dyn, wrapping_add, wrapping_add_dyn, wrapping_mul,
+    wrapping_mul_dyn, wrapping_shl, wrapping_shl_dyn, wrapping_shr, wrapping_shr_dyn, wrapping_sub,
+    wrapping_sub_dyn, Limbs,
+};
+use fast_eint::lane::{self, as_bytes, cast_slice, U256, U512};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+use std::cmp::Ordering;
+
+// Elements up to 8 limbs, zero-extended into E1024 so every result,
+// widening products included, fits before truncating back.
+fn e1024(limbs: &[u64]) -> E1024 {
+    let mut e = E1024::default();
+    e.0[..limbs.len()].copy_from_slice(limbs);
+    e
+}
+
+#[test]
+fn test_generic_matches_asm_256() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+    let mut a = vec![Limbs::<4>::ZERO; 8];
+    let mut b = vec![Limbs::<4>::ZERO; 8];
+    rng.fill_bytes(lane::as_bytes_mut(&mut a));
+    rng.fill_bytes(lane::as_bytes_mut(&mut b));
+    let a256: &[U256] = cast_slice(as_bytes(&a)).unwrap();
+    let b256: &[U256] = cast_slice(as_bytes(&b)).unwrap();
+
+    let mut c = vec![Limbs::<4>::ZERO; 8];
+    let mut expected = vec![U256::ZERO; 8];
+    wrapping_add(&a, &b, &mut c);
+    lane::wrapping_add_256(a256, b256, &mut expected);
+    assert_eq!(as_bytes(&c), as_bytes(&expected));
+    wrapping_sub(&a, &b, &mut c);
+    lane::wrapping_sub_256(a256, b256, &mut expected);
+    assert_eq!(as_bytes(&c), as_bytes(&expected));
+    wrapping_mul(&a, &b, &mut c);
+    lane::wrapping_mul_256(a256, b256, &mut expected);
+    assert_eq!(as_bytes(&c), as_bytes(&expected));
+
+    let mut wide = vec![Limbs::<8>::ZERO; 8];
+    let mut expected = vec![U512::ZERO; 8];
+    widening_mul(&a, &b, &mut wide);
+    lane::widening_mul_256(a256, b256, &mut expected);
+    assert_eq!(as_bytes(&wide), as_bytes(&expected));
+
+    let mut ordering = vec![Ordering::Equal; 8];
+    cmp(&a, &a, &mut ordering);
+    assert!(ordering.iter().all(|o| *o == Ordering::Equal));
+
+    // Shifting out the top 4 bits and back clears just those
+    let mut back = vec![Limbs::<4>::ZERO; 8];
+    wrapping_shl(&a, 260, &mut c);
+    wrapping_shr(&c, 4, &mut back);
+    for i in 0..8 {
+        assert_eq!(back[i].0[..3], a[i].0[..3]);
+        assert_eq!(back[i].0[3], a[i].0[3] & (u64::MAX >> 4));
+    }
+}
+
+#[test]
+fn test_generic_u64_view() {
+    let mut buf = vec![U512::ZERO; 2];
+    lane::as_bytes_mut(&mut buf)[..24].fill(0xff);
+    let limbs: &[u64] = cast_slice(as_bytes(&buf)).unwrap();
+    let mut dst = [0u64; 15];
+    // Three 5-limb elements, the first with its low 192 bits set, plus one
+    let one: Vec<u64> = (0..15).map(|i| (i % 5 == 0) as u64).collect();
+    wrapping_add_dyn(&limbs[..15], &one, &mut dst, 5);
+    assert_eq!(dst, [0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_dyn(
+        limbs in 1usize..=8,
+        a in prop::collection::vec(any::<u64>(), 8 * 8),
+        b in prop::collection::vec(any::<u64>(), 8 * 8),
+        shift in 0u32..2048,
+    ) {
+        let len = a.len() / 8;
+        let (a, b) = (&a[..len * limbs], &b[..len * limbs]);
+
+        let mut sum = vec![0u64; len * limbs];
+        let mut diff = vec![0u64; len * limbs];
+        let mut product = vec![0u64; len * limbs];
+        let mut wide = vec![0u64; 2 * len * limbs];
+        let mut shl = vec![0u64; len * limbs];
+        let mut shr = vec![0u64; len * limbs];
+        let mut ordering = vec![Ordering::Equal; len];
+        wrapping_add_dyn(a, b, &mut sum, limbs);
+        wrapping_sub_dyn(a, b, &mut diff, limbs);
+        wrapping_mul_dyn(a, b, &mut product, limbs);
+        widening_mul_dyn(a, b, &mut wide, limbs);
+        wrapping_shl_dyn(a, shift, &mut shl, limbs);
+        wrapping_shr_dyn(a, shift, &mut shr, limbs);
+        cmp_dyn(a, b, &mut ordering, limbs);
+
+        let s = shift % (64 * limbs as u32);
+        for i in 0..len {
+            let r = i * limbs..(i + 1) * limbs;
+            let (x, y) = (e1024(&a[r.clone()]), e1024(&b[r.clone()]));
+            prop_assert_eq!(&sum[r.clone()], &x.wrapping_add(y).0[..limbs]);
+            prop_assert_eq!(&diff[r.clone()], &x.wrapping_sub(y).0[..limbs]);
+            prop_assert_eq!(&product[r.clone()], &x.wrapping_mul(y).0[..limbs]);
+            prop_assert_eq!(
+                &wide[2 * i * limbs..2 * (i + 1) * limbs],
+                &x.wrapping_mul(y).0[..2 * limbs]
+            );
+            prop_assert_eq!(&shl[r.clone()], &x.wrapping_shl(s).0[..limbs]);
+            prop_assert_eq!(&shr[r.clone()], &x.wrapping_shr(s).0[..limbs]);
+            prop_assert_eq!(ordering[i], x.cmp_u(&y));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use eint::{Eint, E256, E512};
+use fast_eint::lane::{
+    as_bytes, as_bytes_mut, cast_slice, cast_slice_mut, narrowing_right_shift_512,
+    widening_mul_256, wrapping_add_256, CastError, U256, U512,
+};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+#[test]
+fn test_cast_slice_roundtrip() {
+    let mut rng = ChaCha20Rng::seed_from_u64(100);
+
+    let mut lanes = vec![U256::ZERO; 4];
+    rng.fill_bytes(as_bytes_mut(&mut lanes));
+
+    let bytes = as_bytes(&lanes).to_vec();
+    let mut aligned = vec![U512::ZERO; 2];
+    let aligned_bytes = as_bytes_mut(&mut aligned);
+    aligned_bytes.copy_from_slice(&bytes);
+
+    let view: &[U256] = cast_slice(aligned_bytes).unwrap();
+    assert_eq!(view, &lanes[..]);
+    for (i, lane) in view.iter().enumerate() {
+        assert_eq!(lane.to_le_bytes()[..], bytes[i * 32..i * 32 + 32]);
+        assert_eq!(*lane, U256::from_le_bytes(&lane.to_le_bytes()));
+    }
+}
+
+#[test]
+fn test_cast_slice_errors() {
+    let mut buf = vec![U512::ZERO; 2];
+    let bytes = as_bytes_mut(&mut buf);
+
+    assert_eq!(
+        cast_slice::<U256>(&bytes[1..33]).unwrap_err(),
+        CastError::Misaligned
+    );
+    assert_eq!(
+        cast_slice::<U512>(&bytes[0..96]).unwrap_err(),
+        CastError::SizeMismatch
+    );
+    assert_eq!(cast_slice_mut::<U256>(&mut bytes[8..72]).unwrap().len(), 2);
+
+    // Empty buffers have a dangling pointer, which is still a valid view
+    let mut empty: Vec<u8> = Vec::new();
+    assert!(cast_slice::<U256>(&[]).unwrap().is_empty());
+    assert!(cast_slice::<U512>(&[]).unwrap().is_empty());
+    assert!(cast_slice::<U256>(&bytes[1..1]).unwrap().is_empty());
+    assert!(cast_slice_mut::<U256>(&mut empty).unwrap().is_empty());
+    assert!(cast_slice_mut::<U512>(&mut empty).unwrap().is_empty());
+}
+
+#[test]
+fn test_batch_lane_mul_8_256() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+
+    let mut a = vec![U256::ZERO; 8];
+    let mut b = vec![U256::ZERO; 8];
+    rng.fill_bytes(as_bytes_mut(&mut a));
+    rng.fill_bytes(as_bytes_mut(&mut b));
+
+    let mut expected = vec![0u8; 64 * 8];
+    for i in 0..8 {
+        let (lo, hi) =
+            E256::get(&a[i].to_le_bytes()).widening_mul_u(E256::get(&b[i].to_le_bytes()));
+        lo.put(&mut expected[i * 64..i * 64 + 32]);
+        hi.put(&mut expected[i * 64 + 32..i * 64 + 64]);
+    }
+
+    let mut c = vec![U512::ZERO; 8];
+    widening_mul_256(&a, &b, &mut c);
+
+    assert_eq!(as_bytes(&c), &expected[..]);
+}
+
+#[test]
+#[should_panic]
+fn test_lane_length_mismatch() {
+    let a = vec![U256::ZERO; 4];
+    let b = vec![U256::ZERO; 3];
+    let mut c = vec![U256::ZERO; 4];
+
+    wrapping_add_256(&a, &b, &mut c);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_lane_add_256(
+        a in prop::array::uniform16(prop::array::uniform4(0u64..)),
+        b in prop::array::uniform16(prop::array::uniform4(0u64..)),
+    ) {
+        let a: Vec<U256> = a.iter().map(|l| U256(*l)).collect();
+        let b: Vec<U256> = b.iter().map(|l| U256(*l)).collect();
+
+        let mut expected = vec![0u8; 16 * 32];
+        for i in 0..16 {
+            let c = E256::get(&a[i].to_le_bytes()).wrapping_add(E256::get(&b[i].to_le_bytes()));
+            c.put(&mut expected[i * 32..i * 32 + 32]);
+        }
+
+        let mut c = vec![U256::ZERO; 16];
+        wrapping_add_256(&a, &b, &mut c);
+
+        assert_eq!(as_bytes(&c), &expected[..]);
+    }
+
+    #[test]
+    fn random_batch_16_lane_nrshift_512(
+        a in prop::array::uniform16(prop::array::uniform8(0u64..)),
+        shift in 0u32..1024u32,
+    ) {
+        let a: Vec<U512> = a.iter().map(|l| U512(*l)).collect();
+
+        let mut expected = vec![0u8; 16 * 32];
+        for i in 0..16 {
+            let c = E512::get(&a[i].to_le_bytes()).wrapping_shr(shift);
+            c.put_lo(&mut expected[i * 32..i * 32 + 32]);
+        }
+
+        let mut c = vec![U256::ZERO; 16];
+        narrowing_right_shift_512(&a, &mut c, shift);
+
+        assert_eq!(as_bytes(&c), &expected[..]);
+    }
+}
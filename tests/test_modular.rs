@@ -0,0 +1,326 @@
+use eint::{Eint, E256};
+use fast_eint::lane::{widening_mul_256, U256, U512};
+use fast_eint::modular::{Barrett256, Modulus256, Montgomery256};
+use proptest::prelude::*;
+
+// Bit-by-bit long division. eint's own remainder trips a debug assertion in
+// its small-divisor path, and this keeps the reference independent of the
+// Knuth division under test anyway.
+fn reference_rem(x: &[u64], m: &U256) -> U256 {
+    let mut r = [0u64; 5];
+    for bit in (0..x.len() * 64).rev() {
+        let mut carry = (x[bit / 64] >> (bit % 64)) & 1;
+        for limb in r.iter_mut() {
+            let next = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        let ge = (0..5)
+            .rev()
+            .map(|i| (r[i], if i < 4 { m.0[i] } else { 0 }))
+            .find(|(a, b)| a != b)
+            .is_none_or(|(a, b)| a > b);
+        if ge {
+            let mut borrow = 0u64;
+            for (i, limb) in r.iter_mut().enumerate() {
+                let b = if i < 4 { m.0[i] } else { 0 };
+                let (d, o1) = limb.overflowing_sub(b);
+                let (d, o2) = d.overflowing_sub(borrow);
+                *limb = d;
+                borrow = (o1 || o2) as u64;
+            }
+        }
+    }
+    U256([r[0], r[1], r[2], r[3]])
+}
+
+fn e256(a: &U256) -> E256 {
+    E256::get(&a.to_le_bytes())
+}
+
+#[test]
+fn test_zero_modulus() {
+    assert!(Modulus256::new(U256::ZERO).is_none());
+}
+
+#[test]
+fn test_secp256k1_mulmod() {
+    // p = 2^256 - 2^32 - 977
+    let p = U256([
+        0xffff_fffe_ffff_fc2f,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+    ]);
+    let modulus = Modulus256::new(p).unwrap();
+    let minus_one = U256([p.0[0] - 1, p.0[1], p.0[2], p.0[3]]);
+
+    let mut dst = [U256::ZERO; 2];
+    modulus.mulmod(
+        &[minus_one, minus_one],
+        &[minus_one, U256([2, 0, 0, 0])],
+        &mut dst,
+    );
+    assert_eq!(dst[0], U256([1, 0, 0, 0]));
+    assert_eq!(dst[1], U256([p.0[0] - 2, p.0[1], p.0[2], p.0[3]]));
+
+    modulus.addmod(&[minus_one], &[U256([5, 0, 0, 0])], &mut dst[..1]);
+    assert_eq!(dst[0], U256([4, 0, 0, 0]));
+    modulus.submod(&[U256([3, 0, 0, 0])], &[minus_one], &mut dst[..1]);
+    assert_eq!(dst[0], U256([4, 0, 0, 0]));
+}
+
+#[test]
+fn test_even_montgomery_modulus() {
+    assert!(Montgomery256::new(U256([4, 0, 0, 1])).is_none());
+}
+
+#[test]
+fn test_bn256_montgomery() {
+    let p = U256([
+        0x3c20_8c16_d87c_fd47,
+        0x9781_6a91_6871_ca8d,
+        0xb850_45b6_8181_585d,
+        0x3064_4e72_e131_a029,
+    ]);
+    let mont = Montgomery256::new(p).unwrap();
+    let modulus = Modulus256::new(p).unwrap();
+
+    let a = [
+        U256([3, 0, 0, 0]),
+        U256([p.0[0] - 1, p.0[1], p.0[2], p.0[3]]),
+    ];
+    let b = [U256([5, 0, 0, 0]), U256([u64::MAX; 4])];
+    let mut am = [U256::ZERO; 2];
+    let mut bm = [U256::ZERO; 2];
+    mont.to_mont(&a, &mut am);
+    mont.to_mont(&b, &mut bm);
+
+    let mut cm = [U256::ZERO; 2];
+    let mut c = [U256::ZERO; 2];
+    mont.mont_mul(&am, &bm, &mut cm);
+    mont.from_mont(&cm, &mut c);
+    assert_eq!(c[0], U256([15, 0, 0, 0]));
+
+    let mut expected = [U256::ZERO; 2];
+    modulus.mulmod(&a, &b, &mut expected);
+    assert_eq!(c, expected);
+}
+
+// Plain square and multiply on top of the already tested mulmod
+fn expected_powmod(modulus: &Modulus256, a: &U256, e: &U256) -> U256 {
+    let mut acc = [U256([1, 0, 0, 0])];
+    modulus.reduce(&acc.clone(), &mut acc);
+    for bit in (0..256).rev() {
+        modulus.mulmod(&acc.clone(), &acc.clone(), &mut acc);
+        if (e.0[bit / 64] >> (bit % 64)) & 1 == 1 {
+            modulus.mulmod(&acc.clone(), &[*a], &mut acc);
+        }
+    }
+    acc[0]
+}
+
+#[test]
+fn test_secp256k1_invert() {
+    let p = U256([
+        0xffff_fffe_ffff_fc2f,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+    ]);
+    let mont = Montgomery256::new(p).unwrap();
+    let modulus = Modulus256::new(p).unwrap();
+    let a = [
+        U256([2, 0, 0, 0]),
+        U256([p.0[0] - 1, p.0[1], p.0[2], p.0[3]]),
+        U256([0x1234_5678, 0, 0x9abc_def0, 7]),
+    ];
+
+    let mut fermat = [U256::ZERO; 3];
+    let mut gcd = [U256::ZERO; 3];
+    mont.invert_prime(&a, &mut fermat);
+    assert!(mont.invert(&a, &mut gcd));
+    assert_eq!(fermat, gcd);
+
+    let mut one = [U256::ZERO; 3];
+    modulus.mulmod(&a, &gcd, &mut one);
+    assert_eq!(one, [U256([1, 0, 0, 0]); 3]);
+
+    let mut zero = [U256([1, 2, 3, 4])];
+    mont.invert_prime(&[U256::ZERO], &mut zero);
+    assert_eq!(zero[0], U256::ZERO);
+}
+
+#[test]
+fn test_invert_not_coprime() {
+    let mont = Montgomery256::new(U256([15, 0, 0, 0])).unwrap();
+    let mut dst = [U256([1, 2, 3, 4]); 3];
+    assert!(!mont.invert(
+        &[U256([2, 0, 0, 0]), U256([5, 0, 0, 0]), U256::ZERO],
+        &mut dst
+    ));
+    assert_eq!(dst, [U256([8, 0, 0, 0]), U256::ZERO, U256::ZERO]);
+}
+
+#[test]
+fn test_barrett_edge_moduli() {
+    assert!(Barrett256::new(U256::ZERO).is_none());
+
+    let x = [U512([u64::MAX; 8]), U512([7, 0, 0, 0, 0, 0, 0, 1])];
+    for m in [
+        U256([1, 0, 0, 0]),
+        U256([0, 0, 0, 1 << 63]),
+        U256([0, 1, 0, 0]),
+        U256([u64::MAX; 4]),
+    ] {
+        let barrett = Barrett256::new(m).unwrap();
+        let mut dst = [U256::ZERO; 2];
+        barrett.reduce_512(&x, &mut dst);
+        for i in 0..2 {
+            assert_eq!(dst[i], reference_rem(&x[i].0, &m));
+        }
+    }
+}
+
+// Moduli of every limb length, including single-limb ones
+fn modulus() -> impl Strategy<Value = U256> {
+    (1usize..=4, prop::array::uniform4(any::<u64>())).prop_map(|(n, mut limbs)| {
+        for limb in limbs[n..].iter_mut() {
+            *limb = 0;
+        }
+        if limbs[n - 1] == 0 {
+            limbs[n - 1] = 1;
+        }
+        U256(limbs)
+    })
+}
+
+proptest! {
+    #[test]
+    fn random_batch_20_modular(
+        m in modulus(),
+        a in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+        b in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+    ) {
+        let modulus = Modulus256::new(m).unwrap();
+        let a: Vec<U256> = a.into_iter().map(U256).collect();
+        let b: Vec<U256> = b.into_iter().map(U256).collect();
+
+        let mut product = vec![U256::ZERO; 20];
+        modulus.mulmod(&a, &b, &mut product);
+
+        let mut ra = vec![U256::ZERO; 20];
+        let mut rb = vec![U256::ZERO; 20];
+        modulus.reduce(&a, &mut ra);
+        modulus.reduce(&b, &mut rb);
+
+        let mut sum = vec![U256::ZERO; 20];
+        let mut diff = vec![U256::ZERO; 20];
+        modulus.addmod(&ra, &rb, &mut sum);
+        modulus.submod(&ra, &rb, &mut diff);
+
+        for i in 0..20 {
+            prop_assert_eq!(ra[i], reference_rem(&a[i].0, &m));
+
+            let (lo, hi) = e256(&a[i]).widening_mul_u(e256(&b[i]));
+            let wide: Vec<u64> = lo.0.iter().chain(hi.0.iter()).copied().collect();
+            prop_assert_eq!(product[i], reference_rem(&wide, &m));
+
+            let (s, carry) = e256(&ra[i]).overflowing_add_u(e256(&rb[i]));
+            let wide_sum = [s.0[0], s.0[1], s.0[2], s.0[3], carry as u64];
+            prop_assert_eq!(sum[i], reference_rem(&wide_sum, &m));
+
+            // (a - b) mod m == (a + (m - b)) mod m
+            let neg_b = e256(&m).wrapping_sub(e256(&rb[i]));
+            let (s, carry) = e256(&ra[i]).overflowing_add_u(neg_b);
+            let wide_diff = [s.0[0], s.0[1], s.0[2], s.0[3], carry as u64];
+            prop_assert_eq!(diff[i], reference_rem(&wide_diff, &m));
+        }
+    }
+
+    #[test]
+    fn random_batch_20_montgomery(
+        m in modulus().prop_map(|mut m| { m.0[0] |= 1; m }),
+        a in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+        b in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+    ) {
+        let mont = Montgomery256::new(m).unwrap();
+        let modulus = Modulus256::new(m).unwrap();
+        let a: Vec<U256> = a.into_iter().map(U256).collect();
+        let b: Vec<U256> = b.into_iter().map(U256).collect();
+
+        let mut am = vec![U256::ZERO; 20];
+        let mut bm = vec![U256::ZERO; 20];
+        mont.to_mont(&a, &mut am);
+        mont.to_mont(&b, &mut bm);
+
+        let mut product = vec![U256::ZERO; 20];
+        let mut square = vec![U256::ZERO; 20];
+        mont.mont_mul(&am, &bm, &mut product);
+        mont.mont_sqr(&am, &mut square);
+        mont.from_mont(&product.clone(), &mut product);
+        mont.from_mont(&square.clone(), &mut square);
+
+        let mut roundtrip = vec![U256::ZERO; 20];
+        mont.from_mont(&am, &mut roundtrip);
+
+        let mut expected = vec![U256::ZERO; 20];
+        modulus.mulmod(&a, &b, &mut expected);
+        prop_assert_eq!(&product, &expected);
+        modulus.mulmod(&a, &a, &mut expected);
+        prop_assert_eq!(&square, &expected);
+        modulus.reduce(&a, &mut expected);
+        prop_assert_eq!(&roundtrip, &expected);
+    }
+
+    #[test]
+    fn random_batch_20_barrett(
+        m in modulus(),
+        a in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+        b in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+    ) {
+        let barrett = Barrett256::new(m).unwrap();
+        let a: Vec<U256> = a.into_iter().map(U256).collect();
+        let b: Vec<U256> = b.into_iter().map(U256).collect();
+
+        let mut wide = vec![U512::ZERO; 20];
+        widening_mul_256(&a, &b, &mut wide);
+        let mut dst = vec![U256::ZERO; 20];
+        barrett.reduce_512(&wide, &mut dst);
+
+        for i in 0..20 {
+            prop_assert_eq!(dst[i], reference_rem(&wide[i].0, &m));
+        }
+    }
+
+    #[test]
+    fn random_batch_20_powmod_invert(
+        m in modulus().prop_map(|mut m| { m.0[0] |= 1; m }),
+        a in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+        e in prop::collection::vec(prop::array::uniform4(any::<u64>()), 20),
+    ) {
+        let mont = Montgomery256::new(m).unwrap();
+        let modulus = Modulus256::new(m).unwrap();
+        let a: Vec<U256> = a.into_iter().map(U256).collect();
+        let e: Vec<U256> = e.into_iter().map(U256).collect();
+
+        let mut shared = vec![U256::ZERO; 20];
+        let mut each = vec![U256::ZERO; 20];
+        mont.powmod(&a, &e[0], &mut shared);
+        mont.powmod_each(&a, &e, &mut each);
+
+        let mut inv = vec![U256::ZERO; 20];
+        mont.invert(&a, &mut inv);
+        let mut product = vec![U256::ZERO; 20];
+        modulus.mulmod(&a, &inv, &mut product);
+
+        for i in 0..20 {
+            prop_assert_eq!(shared[i], expected_powmod(&modulus, &a[i], &e[0]));
+            prop_assert_eq!(each[i], expected_powmod(&modulus, &a[i], &e[i]));
+            // Either an inverse, or zero for lanes sharing a factor with m
+            if inv[i] != U256::ZERO || m == U256([1, 0, 0, 0]) {
+                prop_assert_eq!(product[i], reference_rem(&[1], &m));
+            }
+        }
+    }
+}
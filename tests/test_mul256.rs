@@ -18,7 +18,7 @@ fn test_single_mul_256() {
     let a = E256::get(&buf_a);
     let b = E256::get(&buf_b);
     let result = {
-        let (lo, hi) = a.clone().widening_mul_u(b.clone());
+        let (lo, hi) = a.widening_mul_u(b);
 
         let mut buf = vec![0u8; 64];
         lo.put(&mut buf[0..32]);
@@ -0,0 +1,83 @@
+use eint::{Eint, E512};
+use fast_eint::{widening_mul_512, widening_mul_512_strided, wrapping_mul_512, wrapping_sub_512};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+fn expected(buf_a: &[u8], buf_b: &[u8], len: usize) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let mut wide = vec![0u8; 128 * len];
+    let mut narrow = vec![0u8; 64 * len];
+    let mut diff = vec![0u8; 64 * len];
+    for i in 0..len {
+        let a = E512::get(&buf_a[i * 64..i * 64 + 64]);
+        let b = E512::get(&buf_b[i * 64..i * 64 + 64]);
+        let (lo, hi) = a.widening_mul_u(b);
+        lo.put(&mut wide[i * 128..i * 128 + 64]);
+        hi.put(&mut wide[i * 128 + 64..i * 128 + 128]);
+        lo.put(&mut narrow[i * 64..i * 64 + 64]);
+        a.wrapping_sub(b).put(&mut diff[i * 64..i * 64 + 64]);
+    }
+    (wide, narrow, diff)
+}
+
+#[test]
+fn test_single_mul_512_all_ones() {
+    let buf_a = vec![0xffu8; 64];
+    let buf_b = vec![0xffu8; 64];
+    let (wide, narrow, _) = expected(&buf_a, &buf_b, 1);
+
+    let mut buf_wide = vec![0u8; 128];
+    let mut buf_narrow = vec![0u8; 64];
+    widening_mul_512(buf_a.as_ptr(), buf_b.as_ptr(), buf_wide.as_mut_ptr(), 1);
+    wrapping_mul_512(buf_a.as_ptr(), buf_b.as_ptr(), buf_narrow.as_mut_ptr(), 1);
+
+    assert_eq!(buf_wide, wide);
+    assert_eq!(buf_narrow, narrow);
+}
+
+#[test]
+fn test_batch_mul_8_512_strided() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+    let mut buf_a = vec![0u8; 64 * 8];
+    let mut buf_b = vec![0u8; 72 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let packed_b: Vec<u8> = buf_b.chunks(72).flat_map(|c| c[..64].to_vec()).collect();
+    let (wide, _, _) = expected(&buf_a, &packed_b, 8);
+
+    let mut buf_result = vec![0u8; 128 * 8];
+    widening_mul_512_strided(
+        buf_a.as_ptr(),
+        64,
+        buf_b.as_ptr(),
+        72,
+        buf_result.as_mut_ptr(),
+        128,
+        8,
+    );
+
+    assert_eq!(buf_result, wide);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_mul_sub_512(
+        a in prop::collection::vec(any::<u8>(), 16 * 64),
+        b in prop::collection::vec(any::<u8>(), 16 * 64),
+    ) {
+        let (wide, narrow, diff) = expected(&a, &b, 16);
+
+        let mut buf_wide = vec![0u8; 128 * 16];
+        let mut buf_narrow = vec![0u8; 64 * 16];
+        let mut buf_diff = vec![0u8; 64 * 16];
+        widening_mul_512(a.as_ptr(), b.as_ptr(), buf_wide.as_mut_ptr(), 16);
+        wrapping_mul_512(a.as_ptr(), b.as_ptr(), buf_narrow.as_mut_ptr(), 16);
+        wrapping_sub_512(a.as_ptr(), b.as_ptr(), buf_diff.as_mut_ptr(), 16);
+
+        assert_eq!(buf_wide, wide);
+        assert_eq!(buf_narrow, narrow);
+        assert_eq!(buf_diff, diff);
+    }
+}
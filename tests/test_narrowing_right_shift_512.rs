@@ -1,5 +1,5 @@
 use eint::{Eint, E512};
-use fast_eint::narrowing_right_shift_512_c as narrowing_right_shift_512;
+use fast_eint::narrowing_right_shift_512;
 use proptest::prelude::*;
 use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
@@ -0,0 +1,97 @@
+use fast_eint::portable;
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+type Strided = fn(*const u8, isize, *const u8, isize, *mut u8, isize, usize);
+
+// The root kernels are checked against eint elsewhere, so the portable ones
+// only need to agree with them. a is walked backwards from an odd offset to
+// cover negative strides and unaligned limbs.
+fn check_strided(seed: u64, len: usize, width: usize, out: usize, root: Strided, port: Strided) {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let mut buf_a = vec![0u8; 3 + (width + 1) * len];
+    let mut buf_b = vec![0u8; width * len];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let mut expected = vec![0u8; out * len];
+    let mut buf_c = vec![0u8; out * len];
+
+    let a_last = 3 + (width + 1) * len.saturating_sub(1);
+    let a = buf_a.as_ptr().wrapping_add(a_last);
+    let a_stride = -(width as isize + 1);
+    let (b, b_stride) = (buf_b.as_ptr(), width as isize);
+    root(
+        a,
+        a_stride,
+        b,
+        b_stride,
+        expected.as_mut_ptr(),
+        out as isize,
+        len,
+    );
+    port(
+        a,
+        a_stride,
+        b,
+        b_stride,
+        buf_c.as_mut_ptr(),
+        out as isize,
+        len,
+    );
+    assert_eq!(buf_c, expected);
+}
+
+#[test]
+fn test_portable_edge_cases() {
+    let ones = [0xffu8; 64];
+    let zero = [0u8; 64];
+    let mut expected = [0u8; 128];
+    let mut buf_c = [0u8; 128];
+
+    fast_eint::widening_mul_512(ones.as_ptr(), ones.as_ptr(), expected.as_mut_ptr(), 1);
+    portable::widening_mul_512(ones.as_ptr(), ones.as_ptr(), buf_c.as_mut_ptr(), 1);
+    assert_eq!(buf_c, expected);
+
+    fast_eint::wrapping_sub_256(zero.as_ptr(), ones.as_ptr(), expected.as_mut_ptr(), 1);
+    portable::wrapping_sub_256(zero.as_ptr(), ones.as_ptr(), buf_c.as_mut_ptr(), 1);
+    assert_eq!(buf_c[..32], expected[..32]);
+
+    assert!(portable::msbc_256(zero.as_ptr(), ones.as_ptr()));
+    assert!(!portable::msbc_256(ones.as_ptr(), ones.as_ptr()));
+}
+
+proptest! {
+    #[test]
+    fn random_batch_portable(seed in 0u64.., len in 0usize..20) {
+        use fast_eint as root;
+        check_strided(seed, len, 32, 32, root::wrapping_add_256_strided, portable::wrapping_add_256_strided);
+        check_strided(seed, len, 64, 64, root::wrapping_add_512_strided, portable::wrapping_add_512_strided);
+        check_strided(seed, len, 32, 32, root::wrapping_sub_256_strided, portable::wrapping_sub_256_strided);
+        check_strided(seed, len, 64, 64, root::wrapping_sub_512_strided, portable::wrapping_sub_512_strided);
+        check_strided(seed, len, 32, 32, root::wrapping_mul_256_strided, portable::wrapping_mul_256_strided);
+        check_strided(seed, len, 64, 64, root::wrapping_mul_512_strided, portable::wrapping_mul_512_strided);
+        check_strided(seed, len, 32, 64, root::widening_mul_256_strided, portable::widening_mul_256_strided);
+        check_strided(seed, len, 64, 128, root::widening_mul_512_strided, portable::widening_mul_512_strided);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let mut buf_a = vec![0u8; 1 + 32 * len];
+        rng.fill_bytes(&mut buf_a);
+        let a = buf_a[1..].as_ptr();
+        let mut expected = vec![0u8; 64 * len];
+        let mut buf_c = vec![0u8; 64 * len];
+        root::widening_sqr_256(a, expected.as_mut_ptr(), len);
+        portable::widening_sqr_256(a, buf_c.as_mut_ptr(), len);
+        prop_assert_eq!(&buf_c, &expected);
+        root::wrapping_sqr_256_strided(a, 32, expected.as_mut_ptr(), 64, len);
+        portable::wrapping_sqr_256_strided(a, 32, buf_c.as_mut_ptr(), 64, len);
+        prop_assert_eq!(&buf_c, &expected);
+
+        for i in 0..len.saturating_sub(1) {
+            let (x, y) = (buf_a[1 + 32 * i..].as_ptr(), buf_a[1 + 32 * (i + 1)..].as_ptr());
+            prop_assert_eq!(portable::msbc_256(x, y), root::msbc_256(x, y));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+#![cfg(target_arch = "x86_64")]
+
+mod common;
+
+use common::supported_backends;
+use eint::{Eint, E256, E512};
+use fast_eint::simd::{self, wrapping_add_256_with, wrapping_add_512_with, wrapping_sub_256_with};
+use fast_eint::{wrapping_add_256, wrapping_add_512, wrapping_sub_256};
+use proptest::prelude::*;
+
+fn check_add_sub_256(buf_a: &[u8], buf_b: &[u8], len: usize) {
+    let mut expected_add = vec![0u8; 32 * len];
+    let mut expected_sub = vec![0u8; 32 * len];
+    for i in 0..len {
+        let a = E256::get(&buf_a[i * 32..i * 32 + 32]);
+        let b = E256::get(&buf_b[i * 32..i * 32 + 32]);
+
+        a.wrapping_add(b)
+            .put(&mut expected_add[i * 32..i * 32 + 32]);
+        a.wrapping_sub(b)
+            .put(&mut expected_sub[i * 32..i * 32 + 32]);
+    }
+
+    // The existing asm path is the reference the vector backends must match
+    let mut asm_add = vec![0u8; 32 * len];
+    let mut asm_sub = vec![0u8; 32 * len];
+    wrapping_add_256(buf_a.as_ptr(), buf_b.as_ptr(), asm_add.as_mut_ptr(), len);
+    wrapping_sub_256(buf_a.as_ptr(), buf_b.as_ptr(), asm_sub.as_mut_ptr(), len);
+    assert_eq!(asm_add, expected_add);
+    assert_eq!(asm_sub, expected_sub);
+
+    for backend in supported_backends() {
+        let mut add = vec![0u8; 32 * len];
+        let mut sub = vec![0u8; 32 * len];
+
+        wrapping_add_256_with(
+            backend,
+            buf_a.as_ptr(),
+            buf_b.as_ptr(),
+            add.as_mut_ptr(),
+            len,
+        );
+        wrapping_sub_256_with(
+            backend,
+            buf_a.as_ptr(),
+            buf_b.as_ptr(),
+            sub.as_mut_ptr(),
+            len,
+        );
+
+        assert_eq!(add, asm_add, "{:?}", backend);
+        assert_eq!(sub, asm_sub, "{:?}", backend);
+    }
+}
+
+fn check_add_512(buf_a: &[u8], buf_b: &[u8], len: usize) {
+    let mut expected = vec![0u8; 64 * len];
+    for i in 0..len {
+        let a = E512::get(&buf_a[i * 64..i * 64 + 64]);
+        let b = E512::get(&buf_b[i * 64..i * 64 + 64]);
+
+        a.wrapping_add(b).put(&mut expected[i * 64..i * 64 + 64]);
+    }
+
+    let mut asm = vec![0u8; 64 * len];
+    wrapping_add_512(buf_a.as_ptr(), buf_b.as_ptr(), asm.as_mut_ptr(), len);
+    assert_eq!(asm, expected);
+
+    for backend in supported_backends() {
+        let mut add = vec![0u8; 64 * len];
+
+        wrapping_add_512_with(
+            backend,
+            buf_a.as_ptr(),
+            buf_b.as_ptr(),
+            add.as_mut_ptr(),
+            len,
+        );
+
+        assert_eq!(add, asm, "{:?}", backend);
+    }
+}
+
+fn limbs_to_bytes(limbs: &[u64]) -> Vec<u8> {
+    limbs.iter().flat_map(|l| l.to_le_bytes()).collect()
+}
+
+#[test]
+fn test_batch_simd_add_carry_chains() {
+    // a = all ones everywhere, b walks a single 1 through every limb so the
+    // carry has to ripple from that limb to the top of its element
+    let len = 16;
+    let a = vec![u64::MAX; 8 * len];
+    let mut b = vec![0u64; 8 * len];
+    for i in 0..len {
+        b[i * 8 + (i % 8)] = 1;
+    }
+    let buf_a = limbs_to_bytes(&a);
+    let buf_b = limbs_to_bytes(&b);
+
+    check_add_sub_256(&buf_a, &buf_b, 2 * len);
+    check_add_sub_256(&buf_b, &buf_a, 2 * len);
+    check_add_512(&buf_a, &buf_b, len);
+    check_add_512(&buf_b, &buf_a, len);
+}
+
+#[test]
+fn test_batch_simd_add_dispatch_256() {
+    let len = 131;
+    let a: Vec<u64> = (0..4 * len as u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect();
+    let b: Vec<u64> = a.iter().map(|l| !l).collect();
+    let buf_a = limbs_to_bytes(&a);
+    let buf_b = limbs_to_bytes(&b);
+
+    let mut expected = vec![0u8; 32 * len];
+    let mut actual = vec![0u8; 32 * len];
+    wrapping_add_256(buf_a.as_ptr(), buf_b.as_ptr(), expected.as_mut_ptr(), len);
+    simd::wrapping_add_256(buf_a.as_ptr(), buf_b.as_ptr(), actual.as_mut_ptr(), len);
+
+    assert_eq!(expected, actual);
+}
+
+// Biased towards limbs that generate or propagate carries and borrows
+fn limb() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        Just(0u64),
+        Just(1u64),
+        Just(u64::MAX),
+        Just(u64::MAX - 1),
+        any::<u64>(),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn random_batch_simd_add_sub(
+        a in prop::collection::vec(prop::array::uniform8(limb()), 0..24),
+        b in prop::collection::vec(prop::array::uniform8(limb()), 24),
+    ) {
+        let len = a.len();
+        let buf_a = limbs_to_bytes(&a.concat());
+        let buf_b = limbs_to_bytes(&b[..len].concat());
+
+        check_add_sub_256(&buf_a, &buf_b, 2 * len);
+        check_add_512(&buf_a, &buf_b, len);
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(target_arch = "x86_64")]
+
+mod common;
+
+use common::supported_backends;
+use eint::{Eint, E256};
+use fast_eint::simd::{self, widening_mul_256_with, wrapping_mul_256_with, Backend};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+fn check_mul(buf_a: &[u8], buf_b: &[u8], len: usize) {
+    let mut expected_wide = vec![0u8; 64 * len];
+    let mut expected_narrow = vec![0u8; 32 * len];
+    for i in 0..len {
+        let a = E256::get(&buf_a[i * 32..i * 32 + 32]);
+        let b = E256::get(&buf_b[i * 32..i * 32 + 32]);
+
+        let (lo, hi) = a.widening_mul_u(b);
+
+        lo.put(&mut expected_wide[i * 64..i * 64 + 32]);
+        hi.put(&mut expected_wide[i * 64 + 32..i * 64 + 64]);
+        lo.put(&mut expected_narrow[i * 32..i * 32 + 32]);
+    }
+
+    for backend in supported_backends() {
+        let mut wide = vec![0u8; 64 * len];
+        let mut narrow = vec![0u8; 32 * len];
+
+        widening_mul_256_with(
+            backend,
+            buf_a.as_ptr(),
+            buf_b.as_ptr(),
+            wide.as_mut_ptr(),
+            len,
+        );
+        wrapping_mul_256_with(
+            backend,
+            buf_a.as_ptr(),
+            buf_b.as_ptr(),
+            narrow.as_mut_ptr(),
+            len,
+        );
+
+        assert_eq!(wide, expected_wide, "{:?}", backend);
+        assert_eq!(narrow, expected_narrow, "{:?}", backend);
+    }
+}
+
+#[test]
+fn test_batch_simd_mul_all_ones() {
+    let buf = vec![0xffu8; 32 * 19];
+
+    check_mul(&buf, &buf, 19);
+}
+
+#[test]
+fn test_batch_simd_mul_128_256() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+
+    let mut buf_a = vec![0u8; 32 * 128];
+    let mut buf_b = vec![0u8; 32 * 128];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+
+    check_mul(&buf_a, &buf_b, 128);
+
+    let mut expected = vec![0u8; 64 * 128];
+    let mut actual = vec![0u8; 64 * 128];
+    widening_mul_256_with(
+        Backend::Scalar,
+        buf_a.as_ptr(),
+        buf_b.as_ptr(),
+        expected.as_mut_ptr(),
+        128,
+    );
+    simd::widening_mul_256(buf_a.as_ptr(), buf_b.as_ptr(), actual.as_mut_ptr(), 128);
+    assert_eq!(expected, actual);
+}
+
+// Biased towards values that stress digit boundaries and carries
+fn limb() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        Just(0u64),
+        Just(u64::MAX),
+        Just((1u64 << 52) - 1),
+        Just(1u64 << 52),
+        any::<u64>(),
+        any::<u64>(),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn random_batch_simd_mul_256(
+        a in prop::collection::vec(prop::array::uniform4(limb()), 0..40),
+        seed in any::<u64>(),
+    ) {
+        let len = a.len();
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        let buf_a: Vec<u8> = a.iter().flatten().flat_map(|l| l.to_le_bytes()).collect();
+        let mut buf_b = vec![0u8; 32 * len];
+        rng.fill_bytes(&mut buf_b);
+
+        check_mul(&buf_a, &buf_b, len);
+        check_mul(&buf_a, &buf_a, len);
+    }
+}
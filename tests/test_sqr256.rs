@@ -0,0 +1,71 @@
+use eint::{Eint, E256};
+use fast_eint::{widening_sqr_256, widening_sqr_256_strided, wrapping_sqr_256};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+fn expected_sqr(buf_a: &[u8], len: usize) -> (Vec<u8>, Vec<u8>) {
+    let mut wide = vec![0u8; 64 * len];
+    let mut narrow = vec![0u8; 32 * len];
+    for i in 0..len {
+        let a = E256::get(&buf_a[i * 32..i * 32 + 32]);
+        let (lo, hi) = a.widening_mul_u(a);
+        lo.put(&mut wide[i * 64..i * 64 + 32]);
+        hi.put(&mut wide[i * 64 + 32..i * 64 + 64]);
+        lo.put(&mut narrow[i * 32..i * 32 + 32]);
+    }
+    (wide, narrow)
+}
+
+#[test]
+fn test_single_sqr_256_all_ones() {
+    let buf_a = vec![0xffu8; 32];
+    let (wide, narrow) = expected_sqr(&buf_a, 1);
+
+    let mut buf_wide = vec![0u8; 64];
+    let mut buf_narrow = vec![0u8; 32];
+    widening_sqr_256(buf_a.as_ptr(), buf_wide.as_mut_ptr(), 1);
+    wrapping_sqr_256(buf_a.as_ptr(), buf_narrow.as_mut_ptr(), 1);
+
+    assert_eq!(buf_wide, wide);
+    assert_eq!(buf_narrow, narrow);
+}
+
+#[test]
+fn test_batch_sqr_8_256_strided() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+    let mut buf_a = vec![0u8; 40 * 8];
+    rng.fill_bytes(&mut buf_a);
+    let packed: Vec<u8> = buf_a.chunks(40).flat_map(|c| c[..32].to_vec()).collect();
+    let (wide, _) = expected_sqr(&packed, 8);
+
+    let mut buf_result = vec![0u8; 72 * 8];
+    widening_sqr_256_strided(buf_a.as_ptr(), 40, buf_result.as_mut_ptr(), 72, 8);
+
+    for i in 0..8 {
+        assert_eq!(buf_result[i * 72..i * 72 + 64], wide[i * 64..i * 64 + 64]);
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_sqr_256(
+        a in prop::array::uniform16(prop::array::uniform32(0u8..)),
+    ) {
+        let mut buf_a = vec![0u8; 32 * 16];
+        for i in 0..16 {
+            buf_a[i * 32..i * 32 + 32].copy_from_slice(&a[i]);
+        }
+        let (wide, narrow) = expected_sqr(&buf_a, 16);
+
+        let mut buf_wide = vec![0u8; 64 * 16];
+        let mut buf_narrow = vec![0u8; 32 * 16];
+        widening_sqr_256(buf_a.as_ptr(), buf_wide.as_mut_ptr(), 16);
+        wrapping_sqr_256(buf_a.as_ptr(), buf_narrow.as_mut_ptr(), 16);
+
+        assert_eq!(buf_wide, wide);
+        assert_eq!(buf_narrow, narrow);
+    }
+}
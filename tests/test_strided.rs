@@ -0,0 +1,208 @@
+use eint::{Eint, E256, E512};
+use fast_eint::{
+    narrowing_right_shift_512_strided, widening_mul_256_strided, wrapping_add_256_strided,
+    wrapping_add_512_strided, wrapping_mul_256_strided, wrapping_sub_256_strided,
+};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+#[test]
+fn test_batch_add_8_256_strided_unaligned() {
+    let mut rng = ChaCha20Rng::seed_from_u64(100);
+
+    // a: stride 40 from offset 3, b: stride 33 from offset 1, c: stride 48 from offset 5
+    let mut buf_a = vec![0u8; 3 + 40 * 8];
+    let mut buf_b = vec![0u8; 1 + 33 * 8];
+    let mut buf_c = vec![0u8; 5 + 48 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    rng.fill_bytes(&mut buf_c);
+    let mut buf_expected = buf_c.clone();
+
+    for i in 0..8 {
+        let a = E256::get(&buf_a[3 + i * 40..3 + i * 40 + 32]);
+        let b = E256::get(&buf_b[1 + i * 33..1 + i * 33 + 32]);
+
+        let c = a.wrapping_add(b);
+
+        c.put(&mut buf_expected[5 + i * 48..5 + i * 48 + 32]);
+    }
+
+    wrapping_add_256_strided(
+        buf_a[3..].as_ptr(),
+        40,
+        buf_b[1..].as_ptr(),
+        33,
+        buf_c[5..].as_mut_ptr(),
+        48,
+        8,
+    );
+
+    assert_eq!(buf_c, buf_expected);
+}
+
+#[test]
+fn test_batch_sub_8_256_negative_stride() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+
+    let mut buf_a = vec![0u8; 32 * 8];
+    let mut buf_b = vec![0u8; 32 * 8];
+    let mut buf_c = vec![0u8; 32 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let mut buf_expected = vec![0u8; 32 * 8];
+
+    // a is walked backwards, so element i of the result pairs a[7 - i] with b[i]
+    for i in 0..8 {
+        let a = E256::get(&buf_a[(7 - i) * 32..(7 - i) * 32 + 32]);
+        let b = E256::get(&buf_b[i * 32..i * 32 + 32]);
+
+        let c = a.wrapping_sub(b);
+
+        c.put(&mut buf_expected[i * 32..i * 32 + 32]);
+    }
+
+    wrapping_sub_256_strided(
+        buf_a[7 * 32..].as_ptr(),
+        -32,
+        buf_b.as_ptr(),
+        32,
+        buf_c.as_mut_ptr(),
+        32,
+        8,
+    );
+
+    assert_eq!(buf_c, buf_expected);
+}
+
+#[test]
+fn test_batch_add_8_512_zero_stride() {
+    let mut rng = ChaCha20Rng::seed_from_u64(222);
+
+    let mut buf_a = vec![0u8; 64 * 8];
+    let mut buf_b = vec![0u8; 64];
+    let mut buf_c = vec![0u8; 64 * 8];
+    rng.fill_bytes(&mut buf_a);
+    rng.fill_bytes(&mut buf_b);
+    let mut buf_expected = vec![0u8; 64 * 8];
+
+    let b = E512::get(&buf_b);
+    for i in 0..8 {
+        let a = E512::get(&buf_a[i * 64..i * 64 + 64]);
+
+        let c = a.wrapping_add(b);
+
+        c.put(&mut buf_expected[i * 64..i * 64 + 64]);
+    }
+
+    wrapping_add_512_strided(
+        buf_a.as_ptr(),
+        64,
+        buf_b.as_ptr(),
+        0,
+        buf_c.as_mut_ptr(),
+        64,
+        8,
+    );
+
+    assert_eq!(buf_c, buf_expected);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_16_mul_256_strided(
+        a in prop::array::uniform16(prop::array::uniform32(0u8..)),
+        b in prop::array::uniform16(prop::array::uniform32(0u8..)),
+        offset in 0usize..8,
+        gap in 0usize..16,
+    ) {
+        let a_stride = 32 + gap;
+        let b_stride = 32 + 2 * gap;
+        let wide_stride = 64 + gap;
+        let narrow_stride = 32 + 3 * gap;
+
+        let mut expected_wide = vec![0u8; offset + wide_stride * 16];
+        let mut expected_narrow = vec![0u8; offset + narrow_stride * 16];
+        let mut buf_a = vec![0u8; offset + a_stride * 16];
+        let mut buf_b = vec![0u8; offset + b_stride * 16];
+        for i in 0..16 {
+            buf_a[offset + i * a_stride..offset + i * a_stride + 32].copy_from_slice(&a[i]);
+            buf_b[offset + i * b_stride..offset + i * b_stride + 32].copy_from_slice(&b[i]);
+
+            let a = E256::get(&a[i]);
+            let b = E256::get(&b[i]);
+
+            let (lo, hi) = a.widening_mul_u(b);
+            let w = offset + i * wide_stride;
+            lo.put(&mut expected_wide[w..w + 32]);
+            hi.put(&mut expected_wide[w + 32..w + 64]);
+            let n = offset + i * narrow_stride;
+            lo.put(&mut expected_narrow[n..n + 32]);
+        }
+
+        let mut buf_wide = vec![0u8; offset + wide_stride * 16];
+        let mut buf_narrow = vec![0u8; offset + narrow_stride * 16];
+
+        widening_mul_256_strided(
+            buf_a[offset..].as_ptr(),
+            a_stride as isize,
+            buf_b[offset..].as_ptr(),
+            b_stride as isize,
+            buf_wide[offset..].as_mut_ptr(),
+            wide_stride as isize,
+            16,
+        );
+        wrapping_mul_256_strided(
+            buf_a[offset..].as_ptr(),
+            a_stride as isize,
+            buf_b[offset..].as_ptr(),
+            b_stride as isize,
+            buf_narrow[offset..].as_mut_ptr(),
+            narrow_stride as isize,
+            16,
+        );
+
+        assert_eq!(expected_wide, buf_wide);
+        assert_eq!(expected_narrow, buf_narrow);
+    }
+
+    #[test]
+    fn random_batch_16_nrshift_512_strided(
+        a in prop::array::uniform16(prop::array::uniform32(0u8..)),
+        b in prop::array::uniform16(prop::array::uniform32(0u8..)),
+        shift in 0u32..1024u32,
+        offset in 0usize..8,
+        gap in 0usize..16,
+    ) {
+        let src_stride = 64 + gap;
+        let dst_stride = 32 + gap;
+
+        let mut expected = vec![0u8; offset + dst_stride * 16];
+        let mut buf_src = vec![0u8; offset + src_stride * 16];
+        for i in 0..16 {
+            let s = offset + i * src_stride;
+            buf_src[s..s + 32].copy_from_slice(&a[i]);
+            buf_src[s + 32..s + 64].copy_from_slice(&b[i]);
+
+            let c = E512::get(&buf_src[s..s + 64]).wrapping_shr(shift);
+            let d = offset + i * dst_stride;
+            c.put_lo(&mut expected[d..d + 32]);
+        }
+
+        let mut buf_dst = vec![0u8; offset + dst_stride * 16];
+
+        narrowing_right_shift_512_strided(
+            buf_src[offset..].as_ptr(),
+            src_stride as isize,
+            buf_dst[offset..].as_mut_ptr(),
+            dst_stride as isize,
+            shift,
+            16,
+        );
+
+        assert_eq!(expected, buf_dst);
+    }
+}
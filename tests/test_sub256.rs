@@ -18,7 +18,7 @@ fn test_single_sub_256() {
     let a = E256::get(&buf_a);
     let b = E256::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_sub(b.clone());
+        let c = a.wrapping_sub(b);
 
         let mut buf = vec![0u8; 32];
         c.put(&mut buf[..]);
@@ -50,7 +50,7 @@ fn test_single_sub_256_same_a() {
     let a = E256::get(&buf_a);
     let b = E256::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_sub(b.clone());
+        let c = a.wrapping_sub(b);
 
         let mut buf = vec![0u8; 32];
         c.put(&mut buf[..]);
@@ -75,7 +75,7 @@ fn test_single_sub_256_same_b() {
     let a = E256::get(&buf_a);
     let b = E256::get(&buf_b);
     let result = {
-        let c = a.clone().wrapping_sub(b.clone());
+        let c = a.wrapping_sub(b);
 
         let mut buf = vec![0u8; 32];
         c.put(&mut buf[..]);
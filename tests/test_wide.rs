@@ -0,0 +1,116 @@
+use eint::{Eint, E1024, E512};
+use fast_eint::lane::{as_bytes, as_bytes_mut, U1024, U2048, U512};
+use fast_eint::wide::{
+    widening_mul_1024, widening_mul_1024_comba, widening_mul_512, widening_mul_512_comba,
+    widening_sqr_512, wrapping_mul_1024, wrapping_mul_1024_comba, wrapping_mul_512,
+    wrapping_mul_512_comba, wrapping_sqr_512,
+};
+use proptest::prelude::*;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+// Operands made of all-zero, all-one and random limbs, which pushes the
+// Karatsuba differences through both signs and zero.
+fn limb() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(0u64), Just(u64::MAX), any::<u64>()]
+}
+
+fn expected_mul_512(a: &[U512], b: &[U512]) -> (Vec<u8>, Vec<u8>) {
+    let mut wide = vec![0u8; 128 * a.len()];
+    let mut narrow = vec![0u8; 64 * a.len()];
+    for i in 0..a.len() {
+        let (lo, hi) =
+            E512::get(&a[i].to_le_bytes()).widening_mul_u(E512::get(&b[i].to_le_bytes()));
+        lo.put(&mut wide[i * 128..i * 128 + 64]);
+        hi.put(&mut wide[i * 128 + 64..i * 128 + 128]);
+        lo.put(&mut narrow[i * 64..i * 64 + 64]);
+    }
+    (wide, narrow)
+}
+
+fn expected_mul_1024(a: &[U1024], b: &[U1024]) -> (Vec<u8>, Vec<u8>) {
+    let mut wide = vec![0u8; 256 * a.len()];
+    let mut narrow = vec![0u8; 128 * a.len()];
+    for i in 0..a.len() {
+        let (lo, hi) =
+            E1024::get(&a[i].to_le_bytes()).widening_mul_u(E1024::get(&b[i].to_le_bytes()));
+        lo.put(&mut wide[i * 256..i * 256 + 128]);
+        hi.put(&mut wide[i * 256 + 128..i * 256 + 256]);
+        lo.put(&mut narrow[i * 128..i * 128 + 128]);
+    }
+    (wide, narrow)
+}
+
+#[test]
+fn test_batch_mul_33_1024() {
+    let mut rng = ChaCha20Rng::seed_from_u64(123);
+
+    let mut a = vec![U1024::ZERO; 33];
+    let mut b = vec![U1024::ZERO; 33];
+    rng.fill_bytes(as_bytes_mut(&mut a));
+    rng.fill_bytes(as_bytes_mut(&mut b));
+    a[0] = U1024([u64::MAX; 16]);
+    b[0] = U1024([u64::MAX; 16]);
+    let (wide, narrow) = expected_mul_1024(&a, &b);
+
+    let mut c = vec![U2048::ZERO; 33];
+    let mut d = vec![U1024::ZERO; 33];
+    widening_mul_1024(&a, &b, &mut c);
+    wrapping_mul_1024(&a, &b, &mut d);
+    assert_eq!(as_bytes(&c), &wide[..]);
+    assert_eq!(as_bytes(&d), &narrow[..]);
+
+    widening_mul_1024_comba(&a, &b, &mut c);
+    wrapping_mul_1024_comba(&a, &b, &mut d);
+    assert_eq!(as_bytes(&c), &wide[..]);
+    assert_eq!(as_bytes(&d), &narrow[..]);
+}
+
+proptest! {
+    #[test]
+    fn random_batch_20_mul_512(
+        a in prop::collection::vec(prop::array::uniform8(limb()), 20),
+        b in prop::collection::vec(prop::array::uniform8(limb()), 20),
+    ) {
+        let a: Vec<U512> = a.into_iter().map(U512).collect();
+        let b: Vec<U512> = b.into_iter().map(U512).collect();
+        let (wide, narrow) = expected_mul_512(&a, &b);
+
+        let mut c = vec![U1024::ZERO; 20];
+        let mut d = vec![U512::ZERO; 20];
+        widening_mul_512(&a, &b, &mut c);
+        wrapping_mul_512(&a, &b, &mut d);
+        prop_assert_eq!(as_bytes(&c), &wide[..]);
+        prop_assert_eq!(as_bytes(&d), &narrow[..]);
+
+        widening_mul_512_comba(&a, &b, &mut c);
+        wrapping_mul_512_comba(&a, &b, &mut d);
+        prop_assert_eq!(as_bytes(&c), &wide[..]);
+        prop_assert_eq!(as_bytes(&d), &narrow[..]);
+
+        let (wide, narrow) = expected_mul_512(&a, &a);
+        widening_sqr_512(&a, &mut c);
+        wrapping_sqr_512(&a, &mut d);
+        prop_assert_eq!(as_bytes(&c), &wide[..]);
+        prop_assert_eq!(as_bytes(&d), &narrow[..]);
+    }
+
+    #[test]
+    fn random_batch_20_mul_1024(
+        a in prop::collection::vec(prop::array::uniform16(limb()), 20),
+        b in prop::collection::vec(prop::array::uniform16(limb()), 20),
+    ) {
+        let a: Vec<U1024> = a.into_iter().map(U1024).collect();
+        let b: Vec<U1024> = b.into_iter().map(U1024).collect();
+        let (wide, narrow) = expected_mul_1024(&a, &b);
+
+        let mut c = vec![U2048::ZERO; 20];
+        let mut d = vec![U1024::ZERO; 20];
+        widening_mul_1024(&a, &b, &mut c);
+        wrapping_mul_1024(&a, &b, &mut d);
+        prop_assert_eq!(as_bytes(&c), &wide[..]);
+        prop_assert_eq!(as_bytes(&d), &narrow[..]);
+    }
+}
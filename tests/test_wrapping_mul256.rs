@@ -18,7 +18,7 @@ fn test_single_wrapping_mul_256() {
     let a = E256::get(&buf_a);
     let b = E256::get(&buf_b);
     let expected = {
-        let c = a.clone().wrapping_mul(b.clone());
+        let c = a.wrapping_mul(b);
 
         let mut buf = vec![0u8; 32];
         c.put(&mut buf);